@@ -50,8 +50,10 @@ impl EventHandler for App {
             top: 0.0,
             bottom: height,
         };
+        let mut layout_engine =
+            DefaultLayoutEngine::default().with_image_size_provider(&self.ui_resources);
         self.ui
-            .layout(ui_space, &mut DefaultLayoutEngine)
+            .layout(ui_space, &mut layout_engine)
             .expect("UI could not layout widgets!");
         self.ui
             .render(&mut GgezRenderer::new(ctx, &mut self.ui_resources))
@@ -1,4 +1,10 @@
-use raui_core::{layout::Layout, renderer::Renderer, widget::unit::WidgetUnit};
+use raui_core::{
+    layout::Layout,
+    loader::{Loader, LoaderError},
+    renderer::Renderer,
+    widget::unit::WidgetUnit,
+};
+use std::io::Read;
 
 #[derive(Debug, Default, Copy, Clone)]
 pub struct BinaryRenderer;
@@ -8,3 +14,30 @@ impl Renderer<Vec<u8>, bincode::Error> for BinaryRenderer {
         bincode::serialize(tree)
     }
 }
+
+struct OffsetReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Read for OffsetReader<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let count = (&self.bytes[self.offset..]).read(buf)?;
+        self.offset += count;
+        Ok(count)
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone)]
+pub struct BinaryLoader;
+
+impl Loader<&[u8], LoaderError> for BinaryLoader {
+    fn load(&mut self, source: &[u8]) -> Result<WidgetUnit, LoaderError> {
+        let mut reader = OffsetReader {
+            bytes: source,
+            offset: 0,
+        };
+        bincode::deserialize_from(&mut reader)
+            .map_err(|error| LoaderError::at_offset(error.to_string(), reader.offset))
+    }
+}
@@ -0,0 +1,379 @@
+use crate::{
+    messenger::MessageSender,
+    widget::{utils::lerp, WidgetId},
+    Scalar,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    f32::consts::{FRAC_PI_2, PI},
+    sync::mpsc::Sender,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Easing {
+    Linear,
+    InQuad,
+    OutQuad,
+    InOutQuad,
+    InCubic,
+    OutCubic,
+    InOutCubic,
+    InSine,
+    OutSine,
+    InOutSine,
+}
+
+impl Default for Easing {
+    fn default() -> Self {
+        Self::Linear
+    }
+}
+
+impl Easing {
+    pub fn apply(self, factor: Scalar) -> Scalar {
+        let t = factor.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::InQuad => t * t,
+            Self::OutQuad => t * (2.0 - t),
+            Self::InOutQuad => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    -1.0 + (4.0 - 2.0 * t) * t
+                }
+            }
+            Self::InCubic => t * t * t,
+            Self::OutCubic => {
+                let t = t - 1.0;
+                t * t * t + 1.0
+            }
+            Self::InOutCubic => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    let t = 2.0 * t - 2.0;
+                    0.5 * t * t * t + 1.0
+                }
+            }
+            Self::InSine => 1.0 - (t * FRAC_PI_2).cos(),
+            Self::OutSine => (t * FRAC_PI_2).sin(),
+            Self::InOutSine => 0.5 * (1.0 - (t * PI).cos()),
+        }
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Keyframe {
+    #[serde(default)]
+    pub time: Scalar,
+    #[serde(default)]
+    pub value: Scalar,
+    #[serde(default)]
+    pub easing: Easing,
+}
+
+impl Keyframe {
+    pub fn new(time: Scalar, value: Scalar, easing: Easing) -> Self {
+        Self {
+            time,
+            value,
+            easing,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Animation {
+    Value {
+        name: String,
+        keyframes: Vec<Keyframe>,
+    },
+    Sequence(Vec<Animation>),
+    Parallel(Vec<Animation>),
+    Looped(Box<Animation>),
+    Delay(Scalar),
+}
+
+impl Animation {
+    pub fn value(name: &str, keyframes: Vec<Keyframe>) -> Self {
+        Self::Value {
+            name: name.to_owned(),
+            keyframes,
+        }
+    }
+
+    pub fn duration(&self) -> Scalar {
+        match self {
+            Self::Value { keyframes, .. } => keyframes
+                .iter()
+                .map(|keyframe| keyframe.time)
+                .fold(0.0, Scalar::max),
+            Self::Sequence(list) => list.iter().map(|item| item.duration()).sum(),
+            Self::Parallel(list) => list
+                .iter()
+                .map(|item| item.duration())
+                .fold(0.0, Scalar::max),
+            Self::Looped(_) => Scalar::INFINITY,
+            Self::Delay(time) => time.max(0.0),
+        }
+    }
+
+    pub fn sample(&self, time: Scalar, result: &mut HashMap<String, Scalar>) {
+        match self {
+            Self::Value { name, keyframes } => {
+                if let Some(value) = Self::sample_keyframes(keyframes, time) {
+                    result.insert(name.to_owned(), value);
+                }
+            }
+            Self::Sequence(list) => {
+                let mut start = 0.0;
+                for item in list {
+                    if time < start {
+                        break;
+                    }
+                    let duration = item.duration();
+                    item.sample((time - start).min(duration), result);
+                    start += duration;
+                }
+            }
+            Self::Parallel(list) => {
+                for item in list {
+                    item.sample(time, result);
+                }
+            }
+            Self::Looped(item) => {
+                let duration = item.duration();
+                if duration > 0.0 && duration.is_finite() {
+                    item.sample(time % duration, result);
+                } else {
+                    item.sample(time, result);
+                }
+            }
+            Self::Delay(_) => {}
+        }
+    }
+
+    fn sample_keyframes(keyframes: &[Keyframe], time: Scalar) -> Option<Scalar> {
+        let first = keyframes.first()?;
+        if time <= first.time {
+            return Some(first.value);
+        }
+        for pair in keyframes.windows(2) {
+            let (from, to) = (&pair[0], &pair[1]);
+            if time < to.time {
+                let span = to.time - from.time;
+                let factor = if span > 0.0 {
+                    (time - from.time) / span
+                } else {
+                    1.0
+                };
+                return Some(lerp(from.value, to.value, to.easing.apply(factor)));
+            }
+        }
+        keyframes.last().map(|keyframe| keyframe.value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AnimationMessage {
+    Completed(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum AnimatorRequest {
+    Play {
+        id: WidgetId,
+        name: String,
+        animation: Animation,
+    },
+    Stop {
+        id: WidgetId,
+        name: String,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct AnimationPlayer {
+    animation: Animation,
+    duration: Scalar,
+    time: Scalar,
+    values: HashMap<String, Scalar>,
+    finished: bool,
+}
+
+impl AnimationPlayer {
+    pub fn new(animation: Animation) -> Self {
+        let duration = animation.duration();
+        let mut values = HashMap::new();
+        animation.sample(0.0, &mut values);
+        Self {
+            animation,
+            duration,
+            time: 0.0,
+            values,
+            finished: false,
+        }
+    }
+
+    #[inline]
+    pub fn time(&self) -> Scalar {
+        self.time
+    }
+
+    #[inline]
+    pub fn duration(&self) -> Scalar {
+        self.duration
+    }
+
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        !self.finished
+    }
+
+    pub fn progress(&self) -> Scalar {
+        if self.duration > 0.0 && self.duration.is_finite() {
+            (self.time / self.duration).clamp(0.0, 1.0)
+        } else if self.is_playing() {
+            0.0
+        } else {
+            1.0
+        }
+    }
+
+    pub fn value(&self, name: &str) -> Option<Scalar> {
+        self.values.get(name).copied()
+    }
+
+    pub fn update(&mut self, delta_time: Scalar) -> bool {
+        if self.finished {
+            return false;
+        }
+        self.time = (self.time + delta_time).min(self.duration);
+        self.animation.sample(self.time, &mut self.values);
+        self.finished = self.time >= self.duration;
+        true
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Animator {
+    players: HashMap<String, AnimationPlayer>,
+}
+
+impl Animator {
+    pub fn player(&self, name: &str) -> Option<&AnimationPlayer> {
+        self.players.get(name)
+    }
+
+    pub fn is_playing(&self, name: &str) -> bool {
+        self.players
+            .get(name)
+            .map(|player| player.is_playing())
+            .unwrap_or_default()
+    }
+
+    pub fn play(&mut self, name: String, animation: Animation) {
+        self.players.insert(name, AnimationPlayer::new(animation));
+    }
+
+    pub fn stop(&mut self, name: &str) {
+        self.players.remove(name);
+    }
+
+    pub fn update(
+        &mut self,
+        id: &WidgetId,
+        delta_time: Scalar,
+        message_sender: &MessageSender,
+    ) -> bool {
+        let mut result = false;
+        for (name, player) in &mut self.players {
+            if player.update(delta_time) {
+                result = true;
+                if !player.is_playing() {
+                    message_sender.write(
+                        id.to_owned(),
+                        Box::new(AnimationMessage::Completed(name.to_owned())),
+                    );
+                }
+            }
+        }
+        result
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AnimatorHandle {
+    id: WidgetId,
+    sender: Sender<AnimatorRequest>,
+}
+
+impl AnimatorHandle {
+    pub fn new(id: WidgetId, sender: Sender<AnimatorRequest>) -> Self {
+        Self { id, sender }
+    }
+
+    pub fn play(&self, name: &str, animation: Animation) -> bool {
+        self.sender
+            .send(AnimatorRequest::Play {
+                id: self.id.clone(),
+                name: name.to_owned(),
+                animation,
+            })
+            .is_ok()
+    }
+
+    pub fn stop(&self, name: &str) -> bool {
+        self.sender
+            .send(AnimatorRequest::Stop {
+                id: self.id.clone(),
+                name: name.to_owned(),
+            })
+            .is_ok()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WidgetAnimator<'a> {
+    animator: Option<&'a Animator>,
+    handle: AnimatorHandle,
+}
+
+impl<'a> WidgetAnimator<'a> {
+    pub fn new(animator: Option<&'a Animator>, handle: AnimatorHandle) -> Self {
+        Self { animator, handle }
+    }
+
+    #[inline]
+    pub fn handle(&self) -> &AnimatorHandle {
+        &self.handle
+    }
+
+    pub fn play(&self, name: &str, animation: Animation) -> bool {
+        self.handle.play(name, animation)
+    }
+
+    pub fn stop(&self, name: &str) -> bool {
+        self.handle.stop(name)
+    }
+
+    pub fn is_playing(&self, name: &str) -> bool {
+        self.animator
+            .map(|animator| animator.is_playing(name))
+            .unwrap_or_default()
+    }
+
+    pub fn progress(&self, name: &str) -> Option<Scalar> {
+        self.animator?.player(name).map(|player| player.progress())
+    }
+
+    pub fn value(&self, name: &str, value: &str) -> Option<Scalar> {
+        self.animator?.player(name)?.value(value)
+    }
+
+    pub fn value_or(&self, name: &str, value: &str, default: Scalar) -> Scalar {
+        self.value(name, value).unwrap_or(default)
+    }
+}
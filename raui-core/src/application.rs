@@ -1,17 +1,38 @@
 use crate::{
+    animator::{Animator, AnimatorHandle, AnimatorRequest, WidgetAnimator},
+    hooks::{HookData, HookEffect, HookUpdate, Hooks},
+    interactions::{
+        focus::{FocusDirection, FocusManager, FocusMessage, WidgetFocus},
+        InteractionsEngine,
+    },
     layout::{Layout, LayoutEngine},
     messenger::{MessageReceiver, MessageSender, Messages, Messenger},
+    props::SharedProps,
     renderer::Renderer,
     signals::{Signal, SignalReceiver, SignalSender},
+    snapshot::{StatesRegistry, StatesSnapshot},
     state::{State, StateData, StateUpdate},
+    store::{Store, StoreView},
+    tasks::{Task, TaskSpawner, TasksExecutor},
+    timers::{TimerRequest, Timers, WidgetTimers},
     widget::{
-        component::WidgetComponent, context::WidgetContext, node::WidgetNode, unit::WidgetUnit,
-        utils::Rect, WidgetId, WidgetLifeCycle, WidgetUnmountClosure,
+        component::{
+            error_boundary::{ErrorBoundaryProps, ErrorBoundarySignal},
+            WidgetComponent,
+        },
+        context::WidgetContext,
+        node::WidgetNode,
+        unit::WidgetUnit,
+        utils::Rect,
+        FnWidget, WidgetId, WidgetLifeCycle, WidgetUnmountClosure,
     },
+    Integer, Scalar,
 };
 use std::{
-    collections::{HashMap, HashSet},
+    any::TypeId,
+    collections::{BTreeMap, HashMap, HashSet},
     convert::TryInto,
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
     sync::mpsc::{channel, Receiver, Sender},
 };
 
@@ -26,6 +47,27 @@ pub struct Application {
     signal_sender: Sender<Signal>,
     signal_receiver: SignalReceiver,
     unmount_closures: HashMap<WidgetId, Vec<Box<WidgetUnmountClosure>>>,
+    focus: FocusManager,
+    cache: HashMap<WidgetId, CacheEntry>,
+    memoization: bool,
+    changed_units: HashSet<WidgetId>,
+    store: Store,
+    store_subscriptions: HashMap<WidgetId, HashSet<TypeId>>,
+    hook_slots: HashMap<WidgetId, Vec<HookData>>,
+    hook_sender: Sender<HookUpdate>,
+    hook_receiver: Receiver<HookUpdate>,
+    states_registry: StatesRegistry,
+    restored_states: HashMap<WidgetId, StateData>,
+    tasks: TasksExecutor,
+    task_sender: Sender<Task>,
+    task_receiver: Receiver<Task>,
+    timers: Timers,
+    timer_sender: Sender<TimerRequest>,
+    timer_receiver: Receiver<TimerRequest>,
+    animators: HashMap<WidgetId, Animator>,
+    animators_changed: HashSet<WidgetId>,
+    animator_sender: Sender<AnimatorRequest>,
+    animator_receiver: Receiver<AnimatorRequest>,
     dirty: bool,
     render_changed: bool,
 }
@@ -43,6 +85,10 @@ impl Application {
         let message_sender = MessageSender::new(message_sender);
         let message_receiver = MessageReceiver::new(message_receiver);
         let (signal_sender, signal_receiver) = channel();
+        let (hook_sender, hook_receiver) = channel();
+        let (task_sender, task_receiver) = channel();
+        let (timer_sender, timer_receiver) = channel();
+        let (animator_sender, animator_receiver) = channel();
         let signal_receiver = SignalReceiver::new(signal_receiver);
         Self {
             tree: Default::default(),
@@ -55,6 +101,27 @@ impl Application {
             signal_sender,
             signal_receiver,
             unmount_closures: Default::default(),
+            focus: Default::default(),
+            cache: Default::default(),
+            memoization: false,
+            changed_units: Default::default(),
+            store: Default::default(),
+            store_subscriptions: Default::default(),
+            hook_slots: Default::default(),
+            hook_sender,
+            hook_receiver,
+            states_registry: Default::default(),
+            restored_states: Default::default(),
+            tasks: Default::default(),
+            task_sender,
+            task_receiver,
+            timers: Default::default(),
+            timer_sender,
+            timer_receiver,
+            animators: Default::default(),
+            animators_changed: Default::default(),
+            animator_sender,
+            animator_receiver,
             dirty: true,
             render_changed: false,
         }
@@ -70,11 +137,119 @@ impl Application {
         self.dirty = true;
     }
 
+    #[inline]
+    pub fn is_memoization_enabled(&self) -> bool {
+        self.memoization
+    }
+
+    #[inline]
+    pub fn set_memoization(&mut self, enabled: bool) {
+        self.memoization = enabled;
+    }
+
     #[inline]
     pub fn does_render_changed(&self) -> bool {
         self.render_changed
     }
 
+    #[inline]
+    pub fn changed_units(&self) -> &HashSet<WidgetId> {
+        &self.changed_units
+    }
+
+    #[inline]
+    pub fn states_registry(&self) -> &StatesRegistry {
+        &self.states_registry
+    }
+
+    #[inline]
+    pub fn states_registry_mut(&mut self) -> &mut StatesRegistry {
+        &mut self.states_registry
+    }
+
+    pub fn snapshot_states(&self) -> StatesSnapshot {
+        let mut states = self
+            .states
+            .iter()
+            .filter_map(|(id, data)| self.states_registry.serialize(id, data))
+            .collect::<Vec<_>>();
+        states.sort_by(|a, b| a.id.as_ref().cmp(b.id.as_ref()));
+        StatesSnapshot { states }
+    }
+
+    pub fn restore_states(&mut self, snapshot: StatesSnapshot) -> usize {
+        let mut result = 0;
+        for state in snapshot.states {
+            if let Some((id, data)) = self.states_registry.deserialize(state) {
+                self.restored_states.insert(id, data);
+                result += 1;
+            }
+        }
+        if result > 0 {
+            self.dirty = true;
+        }
+        result
+    }
+
+    #[inline]
+    pub fn time(&self) -> Scalar {
+        self.timers.time()
+    }
+
+    pub fn update(&mut self, delta_time: Scalar) -> bool {
+        for request in self.timer_receiver.try_iter() {
+            self.timers.request(request);
+        }
+        let mut result = self.timers.update(delta_time, &self.message_sender);
+        self.receive_animator_requests();
+        for (id, animator) in &mut self.animators {
+            if animator.update(id, delta_time, &self.message_sender) {
+                self.animators_changed.insert(id.to_owned());
+                result = true;
+            }
+        }
+        result
+    }
+
+    fn receive_animator_requests(&mut self) {
+        for request in self.animator_receiver.try_iter() {
+            match request {
+                AnimatorRequest::Play {
+                    id,
+                    name,
+                    animation,
+                } => {
+                    self.animators
+                        .entry(id.clone())
+                        .or_default()
+                        .play(name, animation);
+                    self.animators_changed.insert(id);
+                }
+                AnimatorRequest::Stop { id, name } => {
+                    if let Some(animator) = self.animators.get_mut(&id) {
+                        animator.stop(&name);
+                        self.animators_changed.insert(id);
+                    }
+                }
+            }
+        }
+    }
+
+    #[inline]
+    pub fn pending_tasks(&self) -> usize {
+        self.tasks.len()
+    }
+
+    #[inline]
+    pub fn store(&self) -> &Store {
+        &self.store
+    }
+
+    #[inline]
+    pub fn store_mut(&mut self) -> &mut Store {
+        &mut self.store
+    }
+
     #[inline]
     pub fn tree(&self) -> &WidgetNode {
         &self.tree
@@ -139,6 +314,14 @@ impl Application {
         }
     }
 
+    #[inline]
+    pub fn interact<I, R, E>(&mut self, interactions_engine: &mut I) -> Result<R, E>
+    where
+        I: InteractionsEngine<R, E>,
+    {
+        interactions_engine.perform_interactions(self)
+    }
+
     #[inline]
     pub fn messenger(&self) -> &MessageSender {
         &self.message_sender
@@ -149,6 +332,49 @@ impl Application {
         &self.signal_receiver
     }
 
+    #[inline]
+    pub fn focused(&self) -> Option<&WidgetId> {
+        self.focus.focused()
+    }
+
+    #[inline]
+    pub fn focus_manager(&self) -> &FocusManager {
+        &self.focus
+    }
+
+    pub fn focus(&mut self, id: WidgetId) -> bool {
+        if !self.focus.is_focusable(&id) {
+            return false;
+        }
+        if !self.focus.is_focused(&id) {
+            self.change_focus(Some(id));
+        }
+        true
+    }
+
+    pub fn unfocus(&mut self) {
+        if self.focus.focused().is_some() {
+            self.change_focus(None);
+        }
+    }
+
+    pub fn navigate_focus(&mut self, direction: FocusDirection) -> bool {
+        match self.focus.find(direction, &self.layout) {
+            Some(id) => self.focus(id),
+            None => false,
+        }
+    }
+
+    fn change_focus(&mut self, id: Option<WidgetId>) {
+        if let Some(id) = &id {
+            self.message_sender
+                .write(id.to_owned(), Box::new(FocusMessage::Gained));
+        }
+        if let Some(id) = self.focus.focus(id) {
+            self.message_sender.write(id, Box::new(FocusMessage::Lost));
+        }
+    }
+
     #[inline]
     pub fn forced_process(&mut self) -> bool {
         self.dirty = true;
@@ -157,36 +383,130 @@ impl Application {
 
     pub fn process(&mut self) -> bool {
         self.render_changed = false;
-        let changed_states = self
+        let mut changed_states = self
             .state_receivers
             .iter()
             .filter_map(|(id, receiver)| {
                 receiver.try_iter().last().map(|state| (id.clone(), state))
             })
             .collect::<HashMap<_, _>>();
-        let mut messages = self.message_receiver.process();
-        if !self.dirty && changed_states.is_empty() && messages.is_empty() {
+        let restored_ids = self
+            .restored_states
+            .keys()
+            .filter(|id| self.states.contains_key(id))
+            .cloned()
+            .collect::<Vec<_>>();
+        for id in restored_ids {
+            if let Some(state) = self.restored_states.remove(&id) {
+                changed_states.insert(id, state);
+            }
+        }
+        self.tasks.extend(self.task_receiver.try_iter());
+        for (id, message) in self.tasks.poll() {
+            self.message_sender.write(id, message);
+        }
+        let messages = self.message_receiver.process();
+        let store_changed = self.store.take_changed();
+        let mut hooks_changed = HashSet::new();
+        while let Ok((id, index, update)) = self.hook_receiver.try_recv() {
+            if let Some(slot) = self
+                .hook_slots
+                .get_mut(&id)
+                .and_then(|slots| slots.get_mut(index))
+            {
+                (update)(slot);
+                hooks_changed.insert(id);
+            }
+        }
+        let subscribers = self
+            .store_subscriptions
+            .iter()
+            .filter(|(_, subscriptions)| !subscriptions.is_disjoint(&store_changed))
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+        if !self.dirty
+            && changed_states.is_empty()
+            && messages.is_empty()
+            && subscribers.is_empty()
+            && hooks_changed.is_empty()
+            && self.animators_changed.is_empty()
+        {
+            self.changed_units.clear();
             return false;
         }
+        let incremental = !self.dirty;
         self.dirty = false;
-        self.state_receivers.clear();
+        let changed = changed_states
+            .keys()
+            .chain(messages.keys())
+            .cloned()
+            .chain(subscribers)
+            .chain(hooks_changed)
+            .chain(std::mem::take(&mut self.animators_changed))
+            .collect::<HashSet<_>>();
         let old_states = std::mem::replace(&mut self.states, HashMap::new());
         let states = old_states
             .into_iter()
             .chain(changed_states.into_iter())
             .collect::<HashMap<_, _>>();
         let tree = self.tree.clone();
-        let mut used_ids = HashSet::new();
-        let mut new_states = HashMap::new();
+        let previous_tree = std::mem::take(&mut self.rendered_tree);
+        let mut units = HashMap::new();
+        if incremental || self.memoization {
+            Self::collect_units(&previous_tree, &mut units);
+        }
+        let mut context = ProcessContext {
+            states: &states,
+            units,
+            changed,
+            messages,
+            new_states: Default::default(),
+            used_ids: Default::default(),
+            processed_ids: Default::default(),
+            failed_ids: Default::default(),
+            boundaries: 0,
+            changed_units: Default::default(),
+            focusables: Default::default(),
+            state_receivers: std::mem::take(&mut self.state_receivers),
+            cache: std::mem::take(&mut self.cache),
+        };
         let rendered_tree = self.process_node(
             tree,
-            &states,
             vec![],
-            &mut messages,
-            &mut new_states,
-            &mut used_ids,
             "<*>".to_string(),
+            incremental,
+            &SharedProps::default(),
+            &mut context,
         );
+        self.restored_states.clear();
+        let ProcessContext {
+            new_states,
+            used_ids,
+            mut changed_units,
+            focusables,
+            ..
+        } = context;
+        self.focus.set_focusables(focusables);
+        self.store_subscriptions
+            .retain(|id, _| used_ids.contains(id));
+        self.hook_slots.retain(|id, _| used_ids.contains(id));
+        self.tasks.extend(self.task_receiver.try_iter());
+        self.tasks.retain(|id| used_ids.contains(id));
+        for request in self.timer_receiver.try_iter() {
+            self.timers.request(request);
+        }
+        self.timers.retain(|id| used_ids.contains(id));
+        self.receive_animator_requests();
+        self.animators.retain(|id, _| used_ids.contains(id));
+        self.animators_changed.retain(|id| used_ids.contains(id));
+        if let Some(id) = self.focus.focused().cloned() {
+            if !self.focus.is_focusable(&id) {
+                self.focus.focus(None);
+                if used_ids.contains(&id) {
+                    self.message_sender.write(id, Box::new(FocusMessage::Lost));
+                }
+            }
+        }
         self.states = states
             .into_iter()
             .chain(new_states.into_iter())
@@ -194,6 +514,7 @@ impl Application {
                 if used_ids.contains(id) {
                     true
                 } else {
+                    changed_units.insert(id.to_owned());
                     if let Some(closures) = self.unmount_closures.remove(id) {
                         for mut closure in closures {
                             let message_sender = &self.message_sender;
@@ -207,153 +528,467 @@ impl Application {
             })
             .collect();
         if let Ok(tree) = rendered_tree.try_into() {
+            if !incremental {
+                let mut units = HashMap::new();
+                Self::collect_units(&tree, &mut units);
+                changed_units.extend(units.into_keys().map(|id| id.to_owned()));
+            }
+            self.render_changed = !incremental || !changed_units.is_empty();
+            self.changed_units = changed_units;
             self.rendered_tree = tree;
             true
         } else {
+            self.rendered_tree = previous_tree;
+            self.cache.clear();
+            self.changed_units.clear();
             false
         }
     }
 
-    fn process_node<'a>(
+    fn collect_units<'a>(
+        unit: &'a WidgetUnit,
+        result: &mut HashMap<&'a WidgetId, Option<&'a WidgetUnit>>,
+    ) {
+        if let Some(data) = unit.as_data() {
+            result
+                .entry(data.id())
+                .and_modify(|item| *item = None)
+                .or_insert(Some(unit));
+            for child in data.get_children() {
+                Self::collect_units(child, result);
+            }
+        }
+    }
+
+    fn process_node(
         &mut self,
         node: WidgetNode,
-        states: &'a HashMap<WidgetId, StateData>,
-        mut path: Vec<String>,
-        messages: &mut HashMap<WidgetId, Messages>,
-        new_states: &mut HashMap<WidgetId, StateData>,
-        used_ids: &mut HashSet<WidgetId>,
+        path: Vec<String>,
         possible_key: String,
+        reusable: bool,
+        shared_props: &SharedProps,
+        context: &mut ProcessContext,
     ) -> WidgetNode {
         match node {
             WidgetNode::Component(component) => {
-                let WidgetComponent {
-                    processor,
-                    type_name,
-                    key,
-                    props,
-                    listed_slots,
-                    named_slots,
-                } = component;
-                let key = match &key {
-                    Some(key) => key.to_owned(),
-                    None => possible_key.to_owned(),
+                if component.props.is::<ErrorBoundaryProps>() {
+                    self.process_error_boundary(
+                        component,
+                        path,
+                        possible_key,
+                        reusable,
+                        shared_props,
+                        context,
+                    )
+                } else {
+                    self.process_component(
+                        component,
+                        path,
+                        possible_key,
+                        reusable,
+                        shared_props,
+                        context,
+                    )
+                }
+            }
+            _ => node,
+        }
+    }
+
+    fn process_error_boundary(
+        &mut self,
+        component: WidgetComponent,
+        path: Vec<String>,
+        possible_key: String,
+        reusable: bool,
+        shared_props: &SharedProps,
+        context: &mut ProcessContext,
+    ) -> WidgetNode {
+        let mut boundary_path = path.clone();
+        boundary_path.push(match &component.key {
+            Some(key) => key.to_owned(),
+            None => possible_key.to_owned(),
+        });
+        let id = WidgetId::new(component.type_name.to_owned(), boundary_path.clone());
+        let fallback = component
+            .props
+            .read::<ErrorBoundaryProps>()
+            .map(|props| props.fallback.clone())
+            .unwrap_or_default();
+        let processed_start = context.processed_ids.len();
+        let focusables_start = context.focusables.len();
+        let failed_start = context.failed_ids.len();
+        context.boundaries += 1;
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            self.process_component(
+                component,
+                path,
+                possible_key,
+                reusable,
+                shared_props,
+                context,
+            )
+        }));
+        context.boundaries -= 1;
+        match result {
+            Ok(node) => node,
+            Err(error) => {
+                let failed = context.failed_ids.drain(failed_start..).collect::<Vec<_>>();
+                let source = failed
+                    .first()
+                    .or_else(|| context.processed_ids.last())
+                    .cloned();
+                for id in context.processed_ids.drain(processed_start..) {
+                    if !failed.contains(&id) {
+                        context.used_ids.remove(&id);
+                    }
+                }
+                context.focusables.truncate(focusables_start);
+                let message = if let Some(message) = error.downcast_ref::<&str>() {
+                    message.to_string()
+                } else if let Some(message) = error.downcast_ref::<String>() {
+                    message.to_owned()
+                } else {
+                    String::new()
                 };
-                path.push(key.clone());
-                let listed_slots = listed_slots
-                    .into_iter()
-                    .enumerate()
-                    .map(|(i, node)| {
-                        self.process_node(
-                            node,
-                            states,
-                            path.clone(),
-                            messages,
-                            new_states,
-                            used_ids,
-                            format!("<{}>", i),
-                        )
-                    })
-                    .filter(|node| node.is_some())
-                    .collect::<Vec<_>>();
-                let named_slots = named_slots
-                    .into_iter()
-                    .enumerate()
-                    .map(|(i, (name, node))| {
-                        (
-                            name.to_owned(),
-                            self.process_node(
-                                node,
-                                states,
-                                path.clone(),
-                                messages,
-                                new_states,
-                                used_ids,
-                                format!("<{}:{}>", i, name),
-                            ),
-                        )
-                    })
-                    .filter(|(_, node)| node.is_some())
-                    .collect::<HashMap<_, _>>();
-                let id = WidgetId::new(type_name.to_owned(), path.clone());
-                used_ids.insert(id.clone());
-                let (sender, receiver) = channel();
-                let messages_list = match messages.remove(&id) {
-                    Some(messages) => messages,
-                    None => Messages::new(),
+                let signal = ErrorBoundarySignal {
+                    id: id.clone(),
+                    source,
+                    message,
                 };
-                let mut life_cycle = WidgetLifeCycle::default();
-                let (new_node, mounted) = match states.get(&id) {
-                    Some(state) => {
-                        let state = State::new(state, StateUpdate::new(sender.clone()));
-                        let context = WidgetContext {
-                            id: &id,
-                            key: &key,
-                            props: &props,
-                            state,
-                            life_cycle: &mut life_cycle,
-                            named_slots,
-                            listed_slots,
-                        };
-                        ((processor)(context), false)
-                    }
-                    None => {
-                        let state_data = Box::new(()) as StateData;
-                        let state = State::new(&state_data, StateUpdate::new(sender.clone()));
-                        let context = WidgetContext {
-                            id: &id,
-                            key: &key,
-                            props: &props,
-                            state,
-                            life_cycle: &mut life_cycle,
-                            named_slots,
-                            listed_slots,
-                        };
-                        let node = (processor)(context);
-                        new_states.insert(id.clone(), state_data);
-                        (node, true)
-                    }
+                SignalSender::new(id, self.signal_sender.clone()).write(Box::new(signal));
+                self.process_node(
+                    fallback,
+                    boundary_path,
+                    "<fallback>".to_owned(),
+                    false,
+                    shared_props,
+                    context,
+                )
+            }
+        }
+    }
+
+    fn process_component(
+        &mut self,
+        component: WidgetComponent,
+        mut path: Vec<String>,
+        possible_key: String,
+        reusable: bool,
+        shared_props: &SharedProps,
+        context: &mut ProcessContext,
+    ) -> WidgetNode {
+        let key = match &component.key {
+            Some(key) => key.to_owned(),
+            None => possible_key.to_owned(),
+        };
+        path.push(key.clone());
+        let id = WidgetId::new(component.type_name.to_owned(), path.clone());
+        let mut provided_props = None;
+        if let Some(props) = &component.shared_props {
+            let mut props_stack = shared_props.clone();
+            props_stack.provide(props.clone());
+            provided_props = Some(props_stack);
+        }
+        let shared_props = provided_props.as_ref().unwrap_or(shared_props);
+        if let Some(node) = self.reuse_cache(&id, &component, shared_props, reusable, context) {
+            return node;
+        }
+        let reusable = reusable && !context.changed.contains(&id);
+        let inputs = if self.memoization {
+            Some((component.clone(), shared_props.clone()))
+        } else {
+            None
+        };
+        let processed_start = context.processed_ids.len();
+        let focusables_start = context.focusables.len();
+        let WidgetComponent {
+            processor,
+            props,
+            listed_slots,
+            named_slots,
+            ..
+        } = component;
+        let listed_slots = listed_slots
+            .into_iter()
+            .enumerate()
+            .map(|(i, node)| {
+                self.process_node(
+                    node,
+                    path.clone(),
+                    format!("<{}>", i),
+                    reusable,
+                    shared_props,
+                    context,
+                )
+            })
+            .filter(|node| node.is_some())
+            .collect::<Vec<_>>();
+        let named_slots = named_slots
+            .into_iter()
+            .map(|(name, node)| {
+                let possible_key = format!("<#{}>", name);
+                (
+                    name,
+                    self.process_node(
+                        node,
+                        path.clone(),
+                        possible_key,
+                        reusable,
+                        shared_props,
+                        context,
+                    ),
+                )
+            })
+            .filter(|(_, node)| node.is_some())
+            .collect::<BTreeMap<_, _>>();
+        context.used_ids.insert(id.clone());
+        context.processed_ids.push(id.clone());
+        let (sender, receiver) = channel();
+        let messages_list = match context.messages.remove(&id) {
+            Some(messages) => messages,
+            None => Messages::new(),
+        };
+        let mut life_cycle = WidgetLifeCycle::default();
+        let mut focus = WidgetFocus::new(self.focus.is_focused(&id));
+        let mut subscriptions = HashSet::new();
+        let mut hooks = Hooks::new(
+            id.clone(),
+            self.hook_slots.remove(&id).unwrap_or_default(),
+            self.hook_sender.clone(),
+        );
+        let (new_node, mounted) = match context.states.get(&id) {
+            Some(state) => {
+                let state = State::new(state, StateUpdate::new(sender.clone()));
+                let widget_context = WidgetContext {
+                    id: &id,
+                    key: &key,
+                    props: &props,
+                    shared_props,
+                    state,
+                    store: StoreView::new(&self.store, &mut subscriptions),
+                    life_cycle: &mut life_cycle,
+                    hooks: &mut hooks,
+                    tasks: TaskSpawner::new(id.clone(), self.task_sender.clone()),
+                    timers: WidgetTimers::new(id.clone(), self.timer_sender.clone()),
+                    animator: WidgetAnimator::new(
+                        self.animators.get(&id),
+                        AnimatorHandle::new(id.clone(), self.animator_sender.clone()),
+                    ),
+                    focus: &mut focus,
+                    named_slots,
+                    listed_slots,
                 };
-                let (mount, change, unmount) = life_cycle.unwrap();
-                if mounted {
-                    if !mount.is_empty() {
-                        if let Some(state) = new_states.get(&id) {
-                            let state = State::new(state, StateUpdate::new(sender.clone()));
-                            let messenger =
-                                Messenger::new(self.message_sender.clone(), &messages_list);
-                            let signal_sender =
-                                SignalSender::new(id.clone(), self.signal_sender.clone());
-                            for mut closure in mount {
-                                (closure)(&id, &props, &state, &messenger, &signal_sender);
-                            }
-                        }
+                (
+                    Self::call_processor(processor, widget_context, context.boundaries > 0),
+                    false,
+                )
+            }
+            None => {
+                let state_data = self
+                    .restored_states
+                    .remove(&id)
+                    .unwrap_or_else(|| Box::new(()));
+                let state = State::new(&state_data, StateUpdate::new(sender.clone()));
+                let widget_context = WidgetContext {
+                    id: &id,
+                    key: &key,
+                    props: &props,
+                    shared_props,
+                    state,
+                    store: StoreView::new(&self.store, &mut subscriptions),
+                    life_cycle: &mut life_cycle,
+                    hooks: &mut hooks,
+                    tasks: TaskSpawner::new(id.clone(), self.task_sender.clone()),
+                    timers: WidgetTimers::new(id.clone(), self.timer_sender.clone()),
+                    animator: WidgetAnimator::new(
+                        self.animators.get(&id),
+                        AnimatorHandle::new(id.clone(), self.animator_sender.clone()),
+                    ),
+                    focus: &mut focus,
+                    named_slots,
+                    listed_slots,
+                };
+                let node = Self::call_processor(processor, widget_context, context.boundaries > 0);
+                context.new_states.insert(id.clone(), state_data);
+                (node, true)
+            }
+        };
+        let (hook_slots, effects, cleanups) = hooks.unwrap();
+        if !hook_slots.is_empty() {
+            self.hook_slots.insert(id.clone(), hook_slots);
+        }
+        let new_node = match new_node {
+            Ok(node) => node,
+            Err(error) => {
+                self.state_receivers.insert(id.clone(), receiver);
+                context.failed_ids.push(id);
+                resume_unwind(error);
+            }
+        };
+        for effect in effects {
+            let mut effect = Some(effect);
+            if mounted {
+                life_cycle.mount(move |_, _, _, _, _| run_hook_effect(&mut effect));
+            } else {
+                life_cycle.change(move |_, _, _, _, _| run_hook_effect(&mut effect));
+            }
+        }
+        if !cleanups.is_empty() {
+            life_cycle.unmount(move |_, _, _, _| {
+                for cleanup in &cleanups {
+                    let cleanup = cleanup.borrow_mut().take();
+                    if let Some(cleanup) = cleanup {
+                        (cleanup)();
                     }
-                } else if !change.is_empty() {
-                    if let Some(state) = states.get(&id) {
-                        let state = State::new(state, StateUpdate::new(sender.clone()));
-                        let messenger = Messenger::new(self.message_sender.clone(), &messages_list);
-                        let signal_sender =
-                            SignalSender::new(id.clone(), self.signal_sender.clone());
-                        for mut closure in change {
-                            (closure)(&id, &props, &state, &messenger, &signal_sender);
-                        }
+                }
+            });
+        }
+        if subscriptions.is_empty() {
+            self.store_subscriptions.remove(&id);
+        } else {
+            self.store_subscriptions.insert(id.clone(), subscriptions);
+        }
+        if let Some(tab_index) = focus.tab_index() {
+            context.focusables.push((id.clone(), tab_index));
+        }
+        let (mount, change, unmount) = life_cycle.unwrap();
+        if mounted {
+            if !mount.is_empty() {
+                if let Some(state) = context.new_states.get(&id) {
+                    let state = State::new(state, StateUpdate::new(sender.clone()));
+                    let messenger = Messenger::new(self.message_sender.clone(), &messages_list);
+                    let signal_sender = SignalSender::new(id.clone(), self.signal_sender.clone());
+                    for mut closure in mount {
+                        (closure)(&id, &props, &state, &messenger, &signal_sender);
                     }
                 }
-                if !unmount.is_empty() {
-                    self.unmount_closures.insert(id.clone(), unmount);
+            }
+        } else if !change.is_empty() {
+            if let Some(state) = context.states.get(&id) {
+                let state = State::new(state, StateUpdate::new(sender.clone()));
+                let messenger = Messenger::new(self.message_sender.clone(), &messages_list);
+                let signal_sender = SignalSender::new(id.clone(), self.signal_sender.clone());
+                for mut closure in change {
+                    (closure)(&id, &props, &state, &messenger, &signal_sender);
                 }
-                let new_node = self.process_node(
-                    new_node,
-                    states,
-                    path,
-                    messages,
-                    new_states,
-                    used_ids,
-                    possible_key,
-                );
-                self.state_receivers.insert(id, receiver);
-                new_node
             }
-            _ => node,
+        }
+        if !unmount.is_empty() {
+            self.unmount_closures.insert(id.clone(), unmount);
+        }
+        let new_node = self.process_node(
+            new_node,
+            path,
+            possible_key,
+            reusable,
+            shared_props,
+            context,
+        );
+        self.state_receivers.insert(id.clone(), receiver);
+        let unit = match &new_node {
+            WidgetNode::Unit(unit) => unit.as_data().map(|data| data.id().to_owned()),
+            _ => None,
+        };
+        if let Some(unit) = &unit {
+            context.changed_units.insert(unit.to_owned());
+        }
+        context.changed_units.insert(id.clone());
+        self.cache.insert(
+            id,
+            CacheEntry {
+                inputs,
+                unit,
+                ids: context.processed_ids[processed_start..].to_vec(),
+                focusables: context.focusables[focusables_start..].to_vec(),
+            },
+        );
+        new_node
+    }
+
+    fn call_processor(
+        processor: FnWidget,
+        context: WidgetContext,
+        catch: bool,
+    ) -> std::thread::Result<WidgetNode> {
+        if catch {
+            catch_unwind(AssertUnwindSafe(|| (processor)(context)))
+        } else {
+            Ok((processor)(context))
         }
     }
+
+    fn reuse_cache(
+        &mut self,
+        id: &WidgetId,
+        component: &WidgetComponent,
+        shared_props: &SharedProps,
+        reusable: bool,
+        context: &mut ProcessContext,
+    ) -> Option<WidgetNode> {
+        let entry = context.cache.remove(id)?;
+        let unchanged = reusable
+            || (self.memoization
+                && match &entry.inputs {
+                    Some((old_component, old_shared_props)) => {
+                        old_component == component && old_shared_props.props_eq(shared_props)
+                    }
+                    None => false,
+                });
+        if !unchanged
+            || !context.states.contains_key(id)
+            || entry.ids.iter().any(|id| context.changed.contains(id))
+        {
+            return None;
+        }
+        let node = match &entry.unit {
+            Some(unit) => match context.units.get(unit) {
+                Some(Some(unit)) => WidgetNode::Unit((*unit).clone()),
+                _ => return None,
+            },
+            None => WidgetNode::None,
+        };
+        for id in &entry.ids {
+            context.used_ids.insert(id.to_owned());
+            context.processed_ids.push(id.to_owned());
+            if let Some(receiver) = context.state_receivers.remove(id) {
+                self.state_receivers.insert(id.to_owned(), receiver);
+            }
+            if let Some(entry) = context.cache.remove(id) {
+                self.cache.insert(id.to_owned(), entry);
+            }
+        }
+        context.focusables.extend(entry.focusables.iter().cloned());
+        self.cache.insert(id.to_owned(), entry);
+        Some(node)
+    }
+}
+
+fn run_hook_effect(effect: &mut Option<HookEffect>) {
+    if let Some(effect) = effect.take() {
+        (effect)();
+    }
+}
+
+struct CacheEntry {
+    inputs: Option<(WidgetComponent, SharedProps)>,
+    unit: Option<WidgetId>,
+    ids: Vec<WidgetId>,
+    focusables: Vec<(WidgetId, Integer)>,
+}
+
+struct ProcessContext<'a> {
+    states: &'a HashMap<WidgetId, StateData>,
+    units: HashMap<&'a WidgetId, Option<&'a WidgetUnit>>,
+    changed: HashSet<WidgetId>,
+    messages: HashMap<WidgetId, Messages>,
+    new_states: HashMap<WidgetId, StateData>,
+    used_ids: HashSet<WidgetId>,
+    processed_ids: Vec<WidgetId>,
+    failed_ids: Vec<WidgetId>,
+    boundaries: usize,
+    changed_units: HashSet<WidgetId>,
+    focusables: Vec<(WidgetId, Integer)>,
+    state_receivers: HashMap<WidgetId, Receiver<StateData>>,
+    cache: HashMap<WidgetId, CacheEntry>,
 }
@@ -0,0 +1,367 @@
+use crate::widget::{
+    unit::{content::ContentBoxItem, flex::FlexBoxItem, grid::GridBoxItem, WidgetUnit},
+    WidgetId,
+};
+use serde::{Deserialize, Serialize};
+use std::mem::{discriminant, take};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum WidgetUnitPatch {
+    Insert {
+        path: Vec<usize>,
+        index: usize,
+        unit: WidgetUnit,
+    },
+    Remove {
+        path: Vec<usize>,
+        index: usize,
+        id: Option<WidgetId>,
+    },
+    Move {
+        path: Vec<usize>,
+        from: usize,
+        to: usize,
+        id: Option<WidgetId>,
+    },
+    Update {
+        path: Vec<usize>,
+        unit: WidgetUnit,
+    },
+    Replace {
+        path: Vec<usize>,
+        unit: WidgetUnit,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WidgetUnitPatchError {
+    InvalidPath(Vec<usize>),
+    InvalidIndex { path: Vec<usize>, index: usize },
+    ChildrenMismatch(Vec<usize>),
+}
+
+pub fn diff(from: &WidgetUnit, to: &WidgetUnit) -> Vec<WidgetUnitPatch> {
+    let mut result = vec![];
+    if is_same(from, to) {
+        diff_node(from, to, &mut vec![], &mut result);
+    } else {
+        result.push(WidgetUnitPatch::Replace {
+            path: vec![],
+            unit: to.clone(),
+        });
+    }
+    result
+}
+
+pub fn apply(
+    tree: &mut WidgetUnit,
+    patches: &[WidgetUnitPatch],
+) -> Result<(), WidgetUnitPatchError> {
+    for patch in patches {
+        apply_patch(tree, patch)?;
+    }
+    Ok(())
+}
+
+pub fn apply_patch(
+    tree: &mut WidgetUnit,
+    patch: &WidgetUnitPatch,
+) -> Result<(), WidgetUnitPatchError> {
+    match patch {
+        WidgetUnitPatch::Insert { path, index, unit } => {
+            let node = node_mut(tree, path)?;
+            if insert_child(node, *index, unit.clone()) {
+                Ok(())
+            } else {
+                Err(WidgetUnitPatchError::InvalidIndex {
+                    path: path.to_owned(),
+                    index: *index,
+                })
+            }
+        }
+        WidgetUnitPatch::Remove { path, index, .. } => {
+            let node = node_mut(tree, path)?;
+            if remove_child(node, *index) {
+                Ok(())
+            } else {
+                Err(WidgetUnitPatchError::InvalidIndex {
+                    path: path.to_owned(),
+                    index: *index,
+                })
+            }
+        }
+        WidgetUnitPatch::Move { path, from, to, .. } => {
+            let node = node_mut(tree, path)?;
+            if move_child(node, *from, *to) {
+                Ok(())
+            } else {
+                Err(WidgetUnitPatchError::InvalidIndex {
+                    path: path.to_owned(),
+                    index: *from,
+                })
+            }
+        }
+        WidgetUnitPatch::Update { path, unit } => {
+            let node = node_mut(tree, path)?;
+            let count = children_count(node);
+            if discriminant(node) != discriminant(unit) || count != children_count(unit) {
+                return Err(WidgetUnitPatchError::ChildrenMismatch(path.to_owned()));
+            }
+            let children = (0..count)
+                .map(|index| take(child_mut(node, index).unwrap()))
+                .collect::<Vec<_>>();
+            *node = unit.clone();
+            for (index, child) in children.into_iter().enumerate() {
+                *child_mut(node, index).unwrap() = child;
+            }
+            Ok(())
+        }
+        WidgetUnitPatch::Replace { path, unit } => {
+            *node_mut(tree, path)? = unit.clone();
+            Ok(())
+        }
+    }
+}
+
+fn diff_node(
+    from: &WidgetUnit,
+    to: &WidgetUnit,
+    path: &mut Vec<usize>,
+    result: &mut Vec<WidgetUnitPatch>,
+) {
+    if !is_list(from) {
+        let target = shallow(to);
+        if shallow(from) != target {
+            result.push(WidgetUnitPatch::Update {
+                path: path.to_owned(),
+                unit: target,
+            });
+        }
+        if let (WidgetUnit::SizeBox(from), WidgetUnit::SizeBox(to)) = (from, to) {
+            path.push(0);
+            if is_same(&from.slot, &to.slot) {
+                diff_node(&from.slot, &to.slot, path, result);
+            } else {
+                result.push(WidgetUnitPatch::Replace {
+                    path: path.to_owned(),
+                    unit: to.slot.as_ref().clone(),
+                });
+            }
+            path.pop();
+        }
+        return;
+    }
+
+    let from_count = children_count(from);
+    let to_count = children_count(to);
+    let mut used = vec![false; from_count];
+    let matched = (0..to_count)
+        .map(|index| {
+            let target = child(to, index).unwrap();
+            let found =
+                (0..from_count).find(|i| !used[*i] && is_same(child(from, *i).unwrap(), target));
+            if let Some(found) = found {
+                used[found] = true;
+            }
+            found
+        })
+        .collect::<Vec<_>>();
+
+    let mut working = shallow(from);
+    for index in (0..from_count).rev() {
+        if !used[index] {
+            remove_child(&mut working, index);
+            result.push(WidgetUnitPatch::Remove {
+                path: path.to_owned(),
+                index,
+                id: id(child(from, index).unwrap()),
+            });
+        }
+    }
+    let mut current = (0..from_count)
+        .filter(|index| used[*index])
+        .map(Some)
+        .collect::<Vec<_>>();
+    for (index, found) in matched.iter().enumerate() {
+        let patch = match found {
+            Some(found) => {
+                let position = current
+                    .iter()
+                    .position(|item| item == &Some(*found))
+                    .unwrap();
+                if position == index {
+                    continue;
+                }
+                let item = current.remove(position);
+                current.insert(index, item);
+                move_child(&mut working, position, index);
+                WidgetUnitPatch::Move {
+                    path: path.to_owned(),
+                    from: position,
+                    to: index,
+                    id: id(child(from, *found).unwrap()),
+                }
+            }
+            None => {
+                current.insert(index, None);
+                insert_child(&mut working, index, WidgetUnit::None);
+                WidgetUnitPatch::Insert {
+                    path: path.to_owned(),
+                    index,
+                    unit: child(to, index).unwrap().clone(),
+                }
+            }
+        };
+        result.push(patch);
+    }
+    let target = shallow(to);
+    if working != target {
+        result.push(WidgetUnitPatch::Update {
+            path: path.to_owned(),
+            unit: target,
+        });
+    }
+
+    for (index, found) in matched.into_iter().enumerate() {
+        if let Some(found) = found {
+            path.push(index);
+            diff_node(
+                child(from, found).unwrap(),
+                child(to, index).unwrap(),
+                path,
+                result,
+            );
+            path.pop();
+        }
+    }
+}
+
+fn id(unit: &WidgetUnit) -> Option<WidgetId> {
+    unit.as_data().map(|data| data.id().to_owned())
+}
+
+fn is_same(a: &WidgetUnit, b: &WidgetUnit) -> bool {
+    discriminant(a) == discriminant(b)
+        && a.as_data().map(|data| data.id()) == b.as_data().map(|data| data.id())
+}
+
+fn is_list(unit: &WidgetUnit) -> bool {
+    matches!(
+        unit,
+        WidgetUnit::ContentBox(_) | WidgetUnit::FlexBox(_) | WidgetUnit::GridBox(_)
+    )
+}
+
+fn shallow(unit: &WidgetUnit) -> WidgetUnit {
+    let mut result = unit.clone();
+    for index in 0..children_count(&result) {
+        *child_mut(&mut result, index).unwrap() = WidgetUnit::None;
+    }
+    result
+}
+
+fn children_count(unit: &WidgetUnit) -> usize {
+    match unit {
+        WidgetUnit::ContentBox(unit) => unit.items.len(),
+        WidgetUnit::FlexBox(unit) => unit.items.len(),
+        WidgetUnit::GridBox(unit) => unit.items.len(),
+        WidgetUnit::SizeBox(_) => 1,
+        _ => 0,
+    }
+}
+
+fn child(unit: &WidgetUnit, index: usize) -> Option<&WidgetUnit> {
+    match unit {
+        WidgetUnit::ContentBox(unit) => unit.items.get(index).map(|item| &item.slot),
+        WidgetUnit::FlexBox(unit) => unit.items.get(index).map(|item| &item.slot),
+        WidgetUnit::GridBox(unit) => unit.items.get(index).map(|item| &item.slot),
+        WidgetUnit::SizeBox(unit) if index == 0 => Some(&unit.slot),
+        _ => None,
+    }
+}
+
+fn child_mut(unit: &mut WidgetUnit, index: usize) -> Option<&mut WidgetUnit> {
+    match unit {
+        WidgetUnit::ContentBox(unit) => unit.items.get_mut(index).map(|item| &mut item.slot),
+        WidgetUnit::FlexBox(unit) => unit.items.get_mut(index).map(|item| &mut item.slot),
+        WidgetUnit::GridBox(unit) => unit.items.get_mut(index).map(|item| &mut item.slot),
+        WidgetUnit::SizeBox(unit) if index == 0 => Some(&mut unit.slot),
+        _ => None,
+    }
+}
+
+fn node_mut<'a>(
+    tree: &'a mut WidgetUnit,
+    path: &[usize],
+) -> Result<&'a mut WidgetUnit, WidgetUnitPatchError> {
+    let mut node = tree;
+    for index in path {
+        node = match child_mut(node, *index) {
+            Some(node) => node,
+            None => return Err(WidgetUnitPatchError::InvalidPath(path.to_owned())),
+        };
+    }
+    Ok(node)
+}
+
+fn insert_child(unit: &mut WidgetUnit, index: usize, slot: WidgetUnit) -> bool {
+    if index > children_count(unit) {
+        return false;
+    }
+    match unit {
+        WidgetUnit::ContentBox(unit) => unit.items.insert(
+            index,
+            ContentBoxItem {
+                slot,
+                ..Default::default()
+            },
+        ),
+        WidgetUnit::FlexBox(unit) => unit.items.insert(
+            index,
+            FlexBoxItem {
+                slot,
+                ..Default::default()
+            },
+        ),
+        WidgetUnit::GridBox(unit) => unit.items.insert(
+            index,
+            GridBoxItem {
+                slot,
+                ..Default::default()
+            },
+        ),
+        _ => return false,
+    }
+    true
+}
+
+fn remove_child(unit: &mut WidgetUnit, index: usize) -> bool {
+    if index >= children_count(unit) {
+        return false;
+    }
+    match unit {
+        WidgetUnit::ContentBox(unit) => drop(unit.items.remove(index)),
+        WidgetUnit::FlexBox(unit) => drop(unit.items.remove(index)),
+        WidgetUnit::GridBox(unit) => drop(unit.items.remove(index)),
+        _ => return false,
+    }
+    true
+}
+
+fn move_child(unit: &mut WidgetUnit, from: usize, to: usize) -> bool {
+    fn move_item<T>(items: &mut Vec<T>, from: usize, to: usize) -> bool {
+        if from >= items.len() || to >= items.len() {
+            return false;
+        }
+        let item = items.remove(from);
+        items.insert(to, item);
+        true
+    }
+
+    match unit {
+        WidgetUnit::ContentBox(unit) => move_item(&mut unit.items, from, to),
+        WidgetUnit::FlexBox(unit) => move_item(&mut unit.items, from, to),
+        WidgetUnit::GridBox(unit) => move_item(&mut unit.items, from, to),
+        _ => false,
+    }
+}
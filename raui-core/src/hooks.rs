@@ -0,0 +1,223 @@
+use crate::widget::WidgetId;
+use std::{any::Any, cell::RefCell, marker::PhantomData, rc::Rc, sync::mpsc::Sender};
+
+pub type HookData = Box<dyn Any>;
+pub type HookCleanup = Box<dyn FnOnce()>;
+pub type HookEffect = Box<dyn FnOnce()>;
+pub type HookUpdate = (WidgetId, usize, Box<dyn FnOnce(&mut HookData)>);
+
+pub type HookCleanupCell = Rc<RefCell<Option<HookCleanup>>>;
+
+pub struct HookSetter<T> {
+    id: WidgetId,
+    index: usize,
+    sender: Sender<HookUpdate>,
+    _phantom: PhantomData<fn(T)>,
+}
+
+impl<T> Clone for HookSetter<T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id.clone(),
+            index: self.index,
+            sender: self.sender.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> HookSetter<T>
+where
+    T: 'static,
+{
+    pub fn set(&self, value: T) -> bool {
+        self.update(move |_| value)
+    }
+
+    pub fn update<F>(&self, f: F) -> bool
+    where
+        F: 'static + FnOnce(&T) -> T,
+    {
+        let closure = move |data: &mut HookData| {
+            if let Some(data) = data.downcast_mut::<T>() {
+                *data = f(data);
+            }
+        };
+        self.sender
+            .send((self.id.clone(), self.index, Box::new(closure)))
+            .is_ok()
+    }
+}
+
+pub struct HookDispatcher<S, A> {
+    setter: HookSetter<S>,
+    reducer: fn(&S, A) -> S,
+}
+
+impl<S, A> Clone for HookDispatcher<S, A> {
+    fn clone(&self) -> Self {
+        Self {
+            setter: self.setter.clone(),
+            reducer: self.reducer,
+        }
+    }
+}
+
+impl<S, A> HookDispatcher<S, A>
+where
+    S: 'static,
+    A: 'static,
+{
+    pub fn dispatch(&self, action: A) -> bool {
+        let reducer = self.reducer;
+        self.setter.update(move |state| (reducer)(state, action))
+    }
+}
+
+struct EffectSlot<D> {
+    deps: D,
+    cleanup: HookCleanupCell,
+}
+
+pub struct Hooks {
+    id: WidgetId,
+    slots: Vec<HookData>,
+    index: usize,
+    sender: Sender<HookUpdate>,
+    effects: Vec<HookEffect>,
+    cleanups: Vec<HookCleanupCell>,
+}
+
+impl std::fmt::Debug for Hooks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Hooks")
+            .field("id", &self.id)
+            .field("slots", &self.slots.len())
+            .finish()
+    }
+}
+
+impl Hooks {
+    pub fn new(id: WidgetId, slots: Vec<HookData>, sender: Sender<HookUpdate>) -> Self {
+        Self {
+            id,
+            slots,
+            index: 0,
+            sender,
+            effects: vec![],
+            cleanups: vec![],
+        }
+    }
+
+    fn slot<T, F>(&mut self, init: F) -> (usize, &mut T)
+    where
+        T: 'static,
+        F: FnOnce() -> T,
+    {
+        let index = self.index;
+        self.index += 1;
+        if index >= self.slots.len() {
+            self.slots.push(Box::new(init()));
+        } else if !self.slots[index].is::<T>() {
+            self.slots[index] = Box::new(init());
+        }
+        (index, self.slots[index].downcast_mut::<T>().unwrap())
+    }
+
+    fn setter<T>(&self, index: usize) -> HookSetter<T> {
+        HookSetter {
+            id: self.id.clone(),
+            index,
+            sender: self.sender.clone(),
+            _phantom: PhantomData,
+        }
+    }
+
+    pub fn use_state<T, F>(&mut self, init: F) -> (T, HookSetter<T>)
+    where
+        T: 'static + Clone,
+        F: FnOnce() -> T,
+    {
+        let (index, value) = self.slot(init);
+        let value = value.clone();
+        (value, self.setter(index))
+    }
+
+    pub fn use_reducer<S, A, F>(
+        &mut self,
+        reducer: fn(&S, A) -> S,
+        init: F,
+    ) -> (S, HookDispatcher<S, A>)
+    where
+        S: 'static + Clone,
+        F: FnOnce() -> S,
+    {
+        let (value, setter) = self.use_state(init);
+        (value, HookDispatcher { setter, reducer })
+    }
+
+    pub fn use_memo<T, D, F>(&mut self, deps: D, f: F) -> T
+    where
+        T: 'static + Clone,
+        D: 'static + PartialEq,
+        F: FnOnce(&D) -> T,
+    {
+        let (_, slot) = self.slot::<Option<(D, T)>, _>(|| None);
+        match slot {
+            Some((old_deps, value)) if *old_deps == deps => value.clone(),
+            _ => {
+                let value = f(&deps);
+                *slot = Some((deps, value.clone()));
+                value
+            }
+        }
+    }
+
+    pub fn use_effect<D, F>(&mut self, deps: D, effect: F)
+    where
+        D: 'static + PartialEq + Clone,
+        F: 'static + FnOnce(&D) -> Option<HookCleanup>,
+    {
+        let mut changed = true;
+        let (_, slot) = self.slot::<Option<EffectSlot<D>>, _>(|| None);
+        let cleanup = match slot {
+            Some(slot) if slot.deps == deps => {
+                changed = false;
+                slot.cleanup.clone()
+            }
+            Some(slot) => {
+                slot.deps = deps.clone();
+                slot.cleanup.clone()
+            }
+            None => {
+                let cleanup = HookCleanupCell::default();
+                *slot = Some(EffectSlot {
+                    deps: deps.clone(),
+                    cleanup: cleanup.clone(),
+                });
+                cleanup
+            }
+        };
+        if changed {
+            let cell = cleanup.clone();
+            self.effects.push(Box::new(move || {
+                let old_cleanup = cell.borrow_mut().take();
+                if let Some(old_cleanup) = old_cleanup {
+                    (old_cleanup)();
+                }
+                *cell.borrow_mut() = effect(&deps);
+            }));
+        }
+        self.cleanups.push(cleanup);
+    }
+
+    pub fn unwrap(self) -> (Vec<HookData>, Vec<HookEffect>, Vec<HookCleanupCell>) {
+        let Self {
+            slots,
+            effects,
+            cleanups,
+            ..
+        } = self;
+        (slots, effects, cleanups)
+    }
+}
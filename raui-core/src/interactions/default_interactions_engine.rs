@@ -0,0 +1,279 @@
+use crate::{
+    application::Application,
+    interactions::{
+        focus::FocusDirection, hit_test, Interaction, InteractionsEngine, KeyCode, KeyEvent,
+        KeyModifiers, PointerAction, PointerButton, PointerEvent, PointerMessage, ScrollEvent,
+        TextInputEvent,
+    },
+    widget::{utils::Vec2, WidgetId},
+};
+use std::collections::{HashMap, VecDeque};
+
+#[derive(Debug, Clone)]
+pub struct DefaultInteractionsEngine {
+    interactions: VecDeque<Interaction>,
+    pointer_position: Vec2,
+    hovered: Vec<WidgetId>,
+    pressed: HashMap<PointerButton, Vec<WidgetId>>,
+    navigation_keys: HashMap<(KeyCode, KeyModifiers), FocusDirection>,
+}
+
+impl Default for DefaultInteractionsEngine {
+    fn default() -> Self {
+        let shift = KeyModifiers {
+            shift: true,
+            ..Default::default()
+        };
+        let navigation_keys = vec![
+            (
+                (KeyCode::Tab, KeyModifiers::default()),
+                FocusDirection::Next,
+            ),
+            ((KeyCode::Tab, shift), FocusDirection::Previous),
+            ((KeyCode::Up, KeyModifiers::default()), FocusDirection::Up),
+            (
+                (KeyCode::Down, KeyModifiers::default()),
+                FocusDirection::Down,
+            ),
+            (
+                (KeyCode::Left, KeyModifiers::default()),
+                FocusDirection::Left,
+            ),
+            (
+                (KeyCode::Right, KeyModifiers::default()),
+                FocusDirection::Right,
+            ),
+        ]
+        .into_iter()
+        .collect();
+        Self {
+            interactions: Default::default(),
+            pointer_position: Default::default(),
+            hovered: Default::default(),
+            pressed: Default::default(),
+            navigation_keys,
+        }
+    }
+}
+
+impl DefaultInteractionsEngine {
+    #[inline]
+    pub fn pointer_position(&self) -> Vec2 {
+        self.pointer_position
+    }
+
+    #[inline]
+    pub fn hovered(&self) -> &[WidgetId] {
+        &self.hovered
+    }
+
+    pub fn navigation_key(&self, key: KeyCode, modifiers: KeyModifiers) -> Option<FocusDirection> {
+        self.navigation_keys.get(&(key, modifiers)).copied()
+    }
+
+    pub fn bind_navigation_key(
+        &mut self,
+        key: KeyCode,
+        modifiers: KeyModifiers,
+        direction: FocusDirection,
+    ) {
+        self.navigation_keys.insert((key, modifiers), direction);
+    }
+
+    pub fn unbind_navigation_key(&mut self, key: KeyCode, modifiers: KeyModifiers) {
+        self.navigation_keys.remove(&(key, modifiers));
+    }
+
+    pub fn clear_navigation_keys(&mut self) {
+        self.navigation_keys.clear();
+    }
+
+    #[inline]
+    pub fn interact<T>(&mut self, interaction: T)
+    where
+        T: Into<Interaction>,
+    {
+        self.interactions.push_back(interaction.into());
+    }
+
+    #[inline]
+    pub fn pointer(&mut self, event: PointerEvent) {
+        self.interact(event);
+    }
+
+    #[inline]
+    pub fn key(&mut self, event: KeyEvent) {
+        self.interact(event);
+    }
+
+    #[inline]
+    pub fn text_input(&mut self, event: TextInputEvent) {
+        self.interact(event);
+    }
+
+    #[inline]
+    pub fn scroll(&mut self, event: ScrollEvent) {
+        self.interact(event);
+    }
+
+    #[inline]
+    pub fn navigate(&mut self, direction: FocusDirection) {
+        self.interact(direction);
+    }
+
+    fn perform_text_input(application: &mut Application, event: TextInputEvent) -> bool {
+        match application.focused() {
+            Some(id) => application
+                .messenger()
+                .write(id.to_owned(), Box::new(event)),
+            None => false,
+        }
+    }
+
+    fn perform_scroll(&mut self, application: &mut Application, event: ScrollEvent) -> bool {
+        let (hits, mut result) = self.hover(application, event.position);
+        let target = hits.first().cloned().unwrap_or_default();
+        for id in &hits {
+            result |= Self::send(
+                application,
+                id,
+                PointerAction::Scroll(event.delta),
+                &target,
+                event.position,
+            );
+        }
+        result
+    }
+
+    fn perform_key(&self, application: &mut Application, event: KeyEvent) -> bool {
+        let mut result = match application.focused() {
+            Some(id) => application
+                .messenger()
+                .write(id.to_owned(), Box::new(event)),
+            None => false,
+        };
+        if let KeyEvent::Down { key, modifiers } = event {
+            if let Some(direction) = self.navigation_key(key, modifiers) {
+                result |= application.navigate_focus(direction);
+            }
+        }
+        result
+    }
+
+    fn hover(&mut self, application: &Application, position: Vec2) -> (Vec<WidgetId>, bool) {
+        let mut result = false;
+        self.pointer_position = position;
+        let hits = hit_test(
+            application.rendered_tree(),
+            application.layout_data(),
+            position,
+        );
+        let target = hits.first().cloned().unwrap_or_default();
+        let hovered = std::mem::replace(&mut self.hovered, hits.clone());
+        for id in hovered.iter().filter(|id| !hits.contains(id)) {
+            result |= Self::send(application, id, PointerAction::Leave, &target, position);
+        }
+        for id in hits.iter().filter(|id| !hovered.contains(id)) {
+            result |= Self::send(application, id, PointerAction::Enter, &target, position);
+        }
+        (hits, result)
+    }
+
+    fn perform_pointer(&mut self, application: &mut Application, event: PointerEvent) -> bool {
+        let position = event.position();
+        let (hits, mut result) = self.hover(application, position);
+        let target = hits.first().cloned().unwrap_or_default();
+        match event {
+            PointerEvent::Move { .. } => {
+                for id in &hits {
+                    result |= Self::send(application, id, PointerAction::Move, &target, position);
+                }
+            }
+            PointerEvent::Down { button, .. } => {
+                match hits
+                    .iter()
+                    .find(|id| application.focus_manager().is_focusable(id))
+                {
+                    Some(id) => result |= application.focus(id.to_owned()),
+                    None => application.unfocus(),
+                }
+                for id in &hits {
+                    result |= Self::send(
+                        application,
+                        id,
+                        PointerAction::Down(button),
+                        &target,
+                        position,
+                    );
+                }
+                self.pressed.insert(button, hits);
+            }
+            PointerEvent::Up { button, .. } => {
+                let pressed = self.pressed.remove(&button).unwrap_or_default();
+                for id in hits
+                    .iter()
+                    .chain(pressed.iter().filter(|id| !hits.contains(id)))
+                {
+                    result |= Self::send(
+                        application,
+                        id,
+                        PointerAction::Up(button),
+                        &target,
+                        position,
+                    );
+                }
+                for id in hits.iter().filter(|id| pressed.contains(id)) {
+                    result |= Self::send(
+                        application,
+                        id,
+                        PointerAction::Click(button),
+                        &target,
+                        position,
+                    );
+                }
+            }
+        }
+        result
+    }
+
+    fn send(
+        application: &Application,
+        id: &WidgetId,
+        action: PointerAction,
+        target: &WidgetId,
+        position: Vec2,
+    ) -> bool {
+        let local_position = match application.layout_data().items.get(id) {
+            Some(item) => Vec2 {
+                x: position.x - item.ui_space.left,
+                y: position.y - item.ui_space.top,
+            },
+            None => position,
+        };
+        let message = PointerMessage {
+            action,
+            target: target.to_owned(),
+            position,
+            local_position,
+        };
+        application
+            .messenger()
+            .write(id.to_owned(), Box::new(message))
+    }
+}
+
+impl InteractionsEngine<bool, ()> for DefaultInteractionsEngine {
+    fn perform_interactions(&mut self, application: &mut Application) -> Result<bool, ()> {
+        let mut result = false;
+        while let Some(interaction) = self.interactions.pop_front() {
+            result |= match interaction {
+                Interaction::Pointer(event) => self.perform_pointer(application, event),
+                Interaction::Key(event) => self.perform_key(application, event),
+                Interaction::TextInput(event) => Self::perform_text_input(application, event),
+                Interaction::Scroll(event) => self.perform_scroll(application, event),
+                Interaction::Navigate(direction) => application.navigate_focus(direction),
+            };
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{
+    layout::Layout,
+    widget::{utils::Vec2, WidgetId},
+    Integer, Scalar,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FocusDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+    Next,
+    Previous,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FocusMessage {
+    Gained,
+    Lost,
+}
+
+#[derive(Debug, Default, Copy, Clone)]
+pub struct WidgetFocus {
+    focused: bool,
+    tab_index: Option<Integer>,
+}
+
+impl WidgetFocus {
+    pub fn new(focused: bool) -> Self {
+        Self {
+            focused,
+            tab_index: None,
+        }
+    }
+
+    #[inline]
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    #[inline]
+    pub fn is_focusable(&self) -> bool {
+        self.tab_index.is_some()
+    }
+
+    #[inline]
+    pub fn tab_index(&self) -> Option<Integer> {
+        self.tab_index
+    }
+
+    #[inline]
+    pub fn focusable(&mut self, tab_index: Integer) {
+        self.tab_index = Some(tab_index);
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct FocusManager {
+    focused: Option<WidgetId>,
+    focusables: Vec<(WidgetId, Integer)>,
+}
+
+impl FocusManager {
+    #[inline]
+    pub fn focused(&self) -> Option<&WidgetId> {
+        self.focused.as_ref()
+    }
+
+    #[inline]
+    pub fn is_focused(&self, id: &WidgetId) -> bool {
+        self.focused.as_ref() == Some(id)
+    }
+
+    pub fn is_focusable(&self, id: &WidgetId) -> bool {
+        self.focusables.iter().any(|(item, _)| item == id)
+    }
+
+    pub fn focusables(&self) -> impl Iterator<Item = &WidgetId> {
+        self.focusables.iter().map(|(id, _)| id)
+    }
+
+    pub fn set_focusables(&mut self, mut focusables: Vec<(WidgetId, Integer)>) {
+        focusables.sort_by_key(|(_, tab_index)| *tab_index);
+        self.focusables = focusables;
+    }
+
+    pub fn focus(&mut self, id: Option<WidgetId>) -> Option<WidgetId> {
+        std::mem::replace(&mut self.focused, id)
+    }
+
+    pub fn find(&self, direction: FocusDirection, layout: &Layout) -> Option<WidgetId> {
+        if self.focusables.is_empty() {
+            return None;
+        }
+        let index = self
+            .focused
+            .as_ref()
+            .and_then(|id| self.focusables.iter().position(|(item, _)| item == id));
+        let index = match (direction, index) {
+            (FocusDirection::Next, Some(index)) => (index + 1) % self.focusables.len(),
+            (FocusDirection::Previous, Some(index)) => {
+                (index + self.focusables.len() - 1) % self.focusables.len()
+            }
+            (FocusDirection::Previous, None) => self.focusables.len() - 1,
+            (_, None) => 0,
+            (_, Some(index)) => {
+                return self.find_nearest(direction, &self.focusables[index].0, layout)
+            }
+        };
+        Some(self.focusables[index].0.to_owned())
+    }
+
+    fn find_nearest(
+        &self,
+        direction: FocusDirection,
+        from: &WidgetId,
+        layout: &Layout,
+    ) -> Option<WidgetId> {
+        let center = |id: &WidgetId| {
+            layout.items.get(id).map(|item| Vec2 {
+                x: (item.ui_space.left + item.ui_space.right) * 0.5,
+                y: (item.ui_space.top + item.ui_space.bottom) * 0.5,
+            })
+        };
+        let origin = center(from)?;
+        self.focusables
+            .iter()
+            .filter(|(id, _)| id != from)
+            .filter_map(|(id, _)| {
+                let point = center(id)?;
+                let dx = point.x - origin.x;
+                let dy = point.y - origin.y;
+                let (main, cross) = match direction {
+                    FocusDirection::Up => (-dy, dx),
+                    FocusDirection::Down => (dy, dx),
+                    FocusDirection::Left => (-dx, dy),
+                    FocusDirection::Right => (dx, dy),
+                    _ => return None,
+                };
+                if main > 0.0 {
+                    Some((id, main + cross.abs() * 2.0))
+                } else {
+                    None
+                }
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(id, _): (&WidgetId, Scalar)| id.to_owned())
+    }
+}
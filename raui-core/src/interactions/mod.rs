@@ -0,0 +1,252 @@
+pub mod default_interactions_engine;
+pub mod focus;
+
+use crate::{
+    application::Application,
+    interactions::focus::FocusDirection,
+    layout::Layout,
+    widget::{unit::WidgetUnit, utils::Vec2, WidgetId},
+};
+use serde::{Deserialize, Serialize};
+
+pub trait InteractionsEngine<R, E> {
+    fn perform_interactions(&mut self, application: &mut Application) -> Result<R, E>;
+}
+
+impl InteractionsEngine<(), ()> for () {
+    fn perform_interactions(&mut self, _: &mut Application) -> Result<(), ()> {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PointerButton {
+    Primary,
+    Secondary,
+    Middle,
+    Other(u8),
+}
+
+impl Default for PointerButton {
+    fn default() -> Self {
+        Self::Primary
+    }
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum PointerEvent {
+    Move {
+        position: Vec2,
+    },
+    Down {
+        button: PointerButton,
+        position: Vec2,
+    },
+    Up {
+        button: PointerButton,
+        position: Vec2,
+    },
+}
+
+impl PointerEvent {
+    pub fn position(&self) -> Vec2 {
+        match self {
+            Self::Move { position } | Self::Down { position, .. } | Self::Up { position, .. } => {
+                *position
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PointerAction {
+    Enter,
+    Leave,
+    Move,
+    Down(PointerButton),
+    Up(PointerButton),
+    Click(PointerButton),
+    Scroll(Vec2),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PointerMessage {
+    pub action: PointerAction,
+    pub target: WidgetId,
+    pub position: Vec2,
+    pub local_position: Vec2,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum KeyCode {
+    Tab,
+    Enter,
+    Escape,
+    Space,
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Char(char),
+    Other(u32),
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct KeyModifiers {
+    #[serde(default)]
+    pub shift: bool,
+    #[serde(default)]
+    pub control: bool,
+    #[serde(default)]
+    pub alt: bool,
+    #[serde(default)]
+    pub logo: bool,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeyEvent {
+    Down {
+        key: KeyCode,
+        #[serde(default)]
+        modifiers: KeyModifiers,
+    },
+    Up {
+        key: KeyCode,
+        #[serde(default)]
+        modifiers: KeyModifiers,
+    },
+}
+
+impl KeyEvent {
+    pub fn key(&self) -> KeyCode {
+        match self {
+            Self::Down { key, .. } | Self::Up { key, .. } => *key,
+        }
+    }
+
+    pub fn modifiers(&self) -> KeyModifiers {
+        match self {
+            Self::Down { modifiers, .. } | Self::Up { modifiers, .. } => *modifiers,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextInputEvent {
+    #[serde(default)]
+    pub text: String,
+}
+
+#[derive(Debug, Default, Copy, Clone, Serialize, Deserialize)]
+pub struct ScrollEvent {
+    #[serde(default)]
+    pub position: Vec2,
+    #[serde(default)]
+    pub delta: Vec2,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Interaction {
+    Pointer(PointerEvent),
+    Key(KeyEvent),
+    TextInput(TextInputEvent),
+    Scroll(ScrollEvent),
+    Navigate(FocusDirection),
+}
+
+impl From<PointerEvent> for Interaction {
+    fn from(event: PointerEvent) -> Self {
+        Self::Pointer(event)
+    }
+}
+
+impl From<KeyEvent> for Interaction {
+    fn from(event: KeyEvent) -> Self {
+        Self::Key(event)
+    }
+}
+
+impl From<TextInputEvent> for Interaction {
+    fn from(event: TextInputEvent) -> Self {
+        Self::TextInput(event)
+    }
+}
+
+impl From<ScrollEvent> for Interaction {
+    fn from(event: ScrollEvent) -> Self {
+        Self::Scroll(event)
+    }
+}
+
+impl From<FocusDirection> for Interaction {
+    fn from(direction: FocusDirection) -> Self {
+        Self::Navigate(direction)
+    }
+}
+
+pub fn hit_test(tree: &WidgetUnit, layout: &Layout, position: Vec2) -> Vec<WidgetId> {
+    let mut result = vec![];
+    hit_test_node(tree, layout, position, &mut result);
+    result
+}
+
+fn hit_test_node(
+    unit: &WidgetUnit,
+    layout: &Layout,
+    position: Vec2,
+    result: &mut Vec<WidgetId>,
+) -> bool {
+    let (id, children) = match unit {
+        WidgetUnit::None => return false,
+        WidgetUnit::ContentBox(unit) => {
+            let inside = layout
+                .items
+                .get(&unit.id)
+                .map(|item| item.ui_space.contains(position))
+                .unwrap_or(false);
+            if unit.clipping && !inside {
+                return false;
+            }
+            let mut items = unit
+                .items
+                .iter()
+                .map(|item| (item.layout.depth, &item.slot))
+                .collect::<Vec<_>>();
+            items.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+            (
+                &unit.id,
+                items.into_iter().map(|(_, slot)| slot).collect::<Vec<_>>(),
+            )
+        }
+        WidgetUnit::FlexBox(unit) => (
+            &unit.id,
+            unit.items.iter().map(|item| &item.slot).collect::<Vec<_>>(),
+        ),
+        WidgetUnit::GridBox(unit) => (
+            &unit.id,
+            unit.items.iter().map(|item| &item.slot).collect::<Vec<_>>(),
+        ),
+        WidgetUnit::SizeBox(unit) => (&unit.id, vec![unit.slot.as_ref()]),
+        WidgetUnit::ImageBox(unit) => (&unit.id, vec![]),
+        WidgetUnit::TextBox(unit) => (&unit.id, vec![]),
+    };
+    let hit = children
+        .into_iter()
+        .rev()
+        .any(|child| hit_test_node(child, layout, position, result))
+        || layout
+            .items
+            .get(id)
+            .map(|item| item.ui_space.contains(position))
+            .unwrap_or(false);
+    if hit && !id.is_empty() {
+        result.push(id.to_owned());
+    }
+    hit
+}
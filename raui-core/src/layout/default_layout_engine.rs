@@ -1,39 +1,104 @@
 use crate::{
-    layout::{Layout, LayoutEngine, LayoutItem, LayoutNode},
+    layout::{
+        image_size_provider::ImageSizeProvider, text_measurer::TextMeasurer, Layout, LayoutEngine,
+        LayoutItem, LayoutNode,
+    },
     widget::{
         unit::{
             content::ContentBox,
-            flex::FlexBox,
-            grid::GridBox,
-            image::{ImageBox, ImageBoxSizeValue},
+            flex::{FlexBox, FlexBoxDistribution, FlexBoxItem},
+            grid::{GridBox, GridBoxAutoFlow, GridBoxTrackSize},
+            image::{ImageBox, ImageBoxMaterial, ImageBoxSizeValue},
             size::{SizeBox, SizeBoxSizeValue},
             text::{TextBox, TextBoxSizeValue},
             WidgetUnit,
         },
-        utils::{lerp, Rect, Vec2},
+        utils::{lerp, IntRect, Rect, Vec2},
         WidgetId,
     },
-    Scalar,
+    Integer, Scalar,
 };
 use std::collections::HashMap;
 
-#[derive(Debug, Default, Copy, Clone)]
-pub struct DefaultLayoutEngine;
+#[derive(Debug, Copy, Clone)]
+pub struct DefaultLayoutEngine<TM = (), IS = ()> {
+    text_measurer: TM,
+    image_size_provider: IS,
+}
+
+impl Default for DefaultLayoutEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl DefaultLayoutEngine {
-    pub fn layout_node(size_available: Vec2, unit: &WidgetUnit) -> Option<LayoutNode> {
+    pub fn new() -> Self {
+        Self {
+            text_measurer: (),
+            image_size_provider: (),
+        }
+    }
+}
+
+impl<TM, IS> DefaultLayoutEngine<TM, IS>
+where
+    TM: TextMeasurer,
+    IS: ImageSizeProvider,
+{
+    pub fn with_text_measurer<T>(self, text_measurer: T) -> DefaultLayoutEngine<T, IS>
+    where
+        T: TextMeasurer,
+    {
+        DefaultLayoutEngine {
+            text_measurer,
+            image_size_provider: self.image_size_provider,
+        }
+    }
+
+    pub fn with_image_size_provider<T>(self, image_size_provider: T) -> DefaultLayoutEngine<TM, T>
+    where
+        T: ImageSizeProvider,
+    {
+        DefaultLayoutEngine {
+            text_measurer: self.text_measurer,
+            image_size_provider,
+        }
+    }
+
+    #[inline]
+    pub fn text_measurer(&self) -> &TM {
+        &self.text_measurer
+    }
+
+    #[inline]
+    pub fn text_measurer_mut(&mut self) -> &mut TM {
+        &mut self.text_measurer
+    }
+
+    #[inline]
+    pub fn image_size_provider(&self) -> &IS {
+        &self.image_size_provider
+    }
+
+    #[inline]
+    pub fn image_size_provider_mut(&mut self) -> &mut IS {
+        &mut self.image_size_provider
+    }
+
+    pub fn layout_node(&self, size_available: Vec2, unit: &WidgetUnit) -> Option<LayoutNode> {
         match unit {
-            WidgetUnit::ContentBox(b) => Some(Self::layout_content_box(size_available, b)),
-            WidgetUnit::FlexBox(b) => Some(Self::layout_flex_box(size_available, b)),
-            WidgetUnit::GridBox(b) => Self::layout_grid_box(size_available, b),
-            WidgetUnit::SizeBox(b) => Some(Self::layout_size_box(size_available, b)),
-            WidgetUnit::ImageBox(b) => Some(Self::layout_image_box(size_available, b)),
-            WidgetUnit::TextBox(b) => Some(Self::layout_text_box(size_available, b)),
+            WidgetUnit::ContentBox(b) => Some(self.layout_content_box(size_available, b)),
+            WidgetUnit::FlexBox(b) => Some(self.layout_flex_box(size_available, b)),
+            WidgetUnit::GridBox(b) => self.layout_grid_box(size_available, b),
+            WidgetUnit::SizeBox(b) => Some(self.layout_size_box(size_available, b)),
+            WidgetUnit::ImageBox(b) => Some(self.layout_image_box(size_available, b)),
+            WidgetUnit::TextBox(b) => Some(self.layout_text_box(size_available, b)),
             _ => None,
         }
     }
 
-    pub fn layout_content_box(size_available: Vec2, unit: &ContentBox) -> LayoutNode {
+    pub fn layout_content_box(&self, size_available: Vec2, unit: &ContentBox) -> LayoutNode {
         let children = unit
             .items
             .iter()
@@ -52,7 +117,7 @@ impl DefaultLayoutEngine {
                     x: width,
                     y: height,
                 };
-                if let Some(mut child) = Self::layout_node(size, &item.slot) {
+                if let Some(mut child) = self.layout_node(size, &item.slot) {
                     let diff = child.local_space.width() - width;
                     let ox = lerp(0.0, diff, item.layout.align.x);
                     child.local_space.left += left - ox;
@@ -79,15 +144,15 @@ impl DefaultLayoutEngine {
         }
     }
 
-    pub fn layout_flex_box(size_available: Vec2, unit: &FlexBox) -> LayoutNode {
+    pub fn layout_flex_box(&self, size_available: Vec2, unit: &FlexBox) -> LayoutNode {
         if unit.wrap {
-            Self::layout_flex_box_wrapping(size_available, unit)
+            self.layout_flex_box_wrapping(size_available, unit)
         } else {
-            Self::layout_flex_box_no_wrap(size_available, unit)
+            self.layout_flex_box_no_wrap(size_available, unit)
         }
     }
 
-    pub fn layout_flex_box_wrapping(size_available: Vec2, unit: &FlexBox) -> LayoutNode {
+    pub fn layout_flex_box_wrapping(&self, size_available: Vec2, unit: &FlexBox) -> LayoutNode {
         let main_available = if unit.direction.is_horizontal() {
             size_available.x
         } else {
@@ -96,7 +161,6 @@ impl DefaultLayoutEngine {
         let (lines, count) = {
             let mut main = 0.0;
             let mut cross: Scalar = 0.0;
-            let mut grow = 0.0;
             let items = unit
                 .items
                 .iter()
@@ -108,11 +172,13 @@ impl DefaultLayoutEngine {
             for item in items {
                 let local_main = item.basis.unwrap_or_else(|| {
                     if unit.direction.is_horizontal() {
-                        Self::calc_unit_min_width(&item.slot)
+                        self.calc_unit_min_width(&item.slot)
                     } else {
-                        Self::calc_unit_min_height(&item.slot)
+                        self.calc_unit_min_height(&item.slot)
                     }
                 });
+                let local_main =
+                    Self::clamp_flex_item(item, local_main, unit.direction.is_horizontal());
                 let local_main = local_main
                     + if unit.direction.is_horizontal() {
                         item.margin.left + item.margin.right
@@ -120,10 +186,12 @@ impl DefaultLayoutEngine {
                         item.margin.top + item.margin.bottom
                     };
                 let local_cross = if unit.direction.is_horizontal() {
-                    Self::calc_unit_min_height(&item.slot)
+                    self.calc_unit_min_height(&item.slot)
                 } else {
-                    Self::calc_unit_min_width(&item.slot)
+                    self.calc_unit_min_width(&item.slot)
                 };
+                let local_cross =
+                    Self::clamp_flex_item(item, local_cross, unit.direction.is_vertical());
                 let local_cross = local_cross
                     + if unit.direction.is_horizontal() {
                         item.margin.top + item.margin.bottom
@@ -132,38 +200,62 @@ impl DefaultLayoutEngine {
                     };
                 if !line.is_empty() && main + local_main > main_available {
                     main += line.len().checked_sub(1).unwrap_or(0) as Scalar * unit.separation;
-                    lines.push((main, cross, grow, std::mem::replace(&mut line, vec![])));
+                    lines.push((main, cross, std::mem::replace(&mut line, vec![])));
                     main = 0.0;
                     cross = 0.0;
-                    grow = 0.0;
                 }
                 main += local_main;
                 cross = cross.max(local_cross);
-                grow += item.grow;
                 line.push((item, local_main, local_cross));
             }
             main += line.len().checked_sub(1).unwrap_or(0) as Scalar * unit.separation;
-            lines.push((main, cross, grow, line));
+            lines.push((main, cross, line));
             (lines, count)
         };
+        let cross_size_available = if unit.direction.is_horizontal() {
+            size_available.y
+        } else {
+            size_available.x
+        };
+        let lines_cross = lines.iter().map(|line| line.1).sum::<Scalar>()
+            + lines.len().saturating_sub(1) as Scalar * unit.separation;
+        let (cross_offset, cross_spacing) = unit
+            .align_content
+            .offset_and_spacing(cross_size_available - lines_cross, lines.len());
+        let cross_trailing = Self::flex_trailing_space(
+            unit.align_content,
+            cross_size_available - lines_cross,
+            lines.len(),
+        );
+        let lines_separation = unit.separation + cross_spacing;
         let mut children = Vec::with_capacity(count);
         let mut main_max: Scalar = 0.0;
-        let mut cross_max = 0.0;
-        for (main, cross_available, grow, items) in lines {
-            let diff = main_available - main;
-            let mut new_main = 0.0;
-            let mut new_cross: Scalar = 0.0;
-            for (item, local_main, local_cross) in items {
-                let child_main = if main < main_available {
-                    local_main
-                        + if grow > 0.0 {
-                            diff * item.grow / grow
+        let mut cross_max = cross_offset;
+        for (main, cross_available, items) in lines {
+            let separations = items.len().saturating_sub(1) as Scalar * unit.separation;
+            let mains = Self::distribute_flex_items(
+                &items
+                    .iter()
+                    .map(|(item, local_main, _)| {
+                        let factor = if main < main_available {
+                            item.grow
                         } else {
                             0.0
-                        }
-                } else {
-                    local_main
-                };
+                        };
+                        (*item, *local_main, factor)
+                    })
+                    .collect::<Vec<_>>(),
+                main_available - separations,
+                unit.direction.is_horizontal(),
+            );
+            let diff = main_available - separations - mains.iter().sum::<Scalar>();
+            let (main_offset, main_spacing) =
+                unit.justify_content.offset_and_spacing(diff, items.len());
+            let main_trailing = Self::flex_trailing_space(unit.justify_content, diff, items.len());
+            let separation = unit.separation + main_spacing;
+            let mut new_main = main_offset;
+            let mut new_cross: Scalar = 0.0;
+            for ((item, _, local_cross), child_main) in items.into_iter().zip(mains) {
                 let child_main = (child_main
                     - if unit.direction.is_horizontal() {
                         item.margin.left + item.margin.right
@@ -179,6 +271,8 @@ impl DefaultLayoutEngine {
                     })
                 .max(0.0);
                 let child_cross = lerp(child_cross, cross_available, item.fill);
+                let child_cross =
+                    Self::clamp_flex_item(item, child_cross, unit.direction.is_vertical());
                 let rect = if unit.direction.is_horizontal() {
                     Vec2 {
                         x: child_main,
@@ -190,7 +284,7 @@ impl DefaultLayoutEngine {
                         y: child_main,
                     }
                 };
-                if let Some(mut child) = Self::layout_node(rect, &item.slot) {
+                if let Some(mut child) = self.layout_node(rect, &item.slot) {
                     if unit.direction.is_horizontal() {
                         if unit.direction.is_order_ascending() {
                             child.local_space.left += new_main + item.margin.left;
@@ -231,15 +325,15 @@ impl DefaultLayoutEngine {
                         child.local_space.right += cross_max + item.margin.left + diff;
                         new_cross = new_cross.max(rect.x);
                     }
-                    new_main += unit.separation;
+                    new_main += separation;
                     children.push(child);
                 }
             }
-            new_main = (new_main - unit.separation).max(0.0);
+            new_main = (new_main - separation).max(0.0) + main_trailing;
             main_max = main_max.max(new_main);
-            cross_max += new_cross + unit.separation;
+            cross_max += new_cross + lines_separation;
         }
-        cross_max = (cross_max - unit.separation).max(0.0);
+        cross_max = (cross_max - lines_separation).max(0.0) + cross_trailing;
         let local_space = if unit.direction.is_horizontal() {
             Rect {
                 left: 0.0,
@@ -262,7 +356,7 @@ impl DefaultLayoutEngine {
         }
     }
 
-    pub fn layout_flex_box_no_wrap(size_available: Vec2, unit: &FlexBox) -> LayoutNode {
+    pub fn layout_flex_box_no_wrap(&self, size_available: Vec2, unit: &FlexBox) -> LayoutNode {
         let (main_available, cross_available) = if unit.direction.is_horizontal() {
             (size_available.x, size_available.y)
         } else {
@@ -270,8 +364,6 @@ impl DefaultLayoutEngine {
         };
         let mut main = 0.0;
         let mut cross: Scalar = 0.0;
-        let mut grow = 0.0;
-        let mut shrink = 0.0;
         let items = unit
             .items
             .iter()
@@ -282,11 +374,13 @@ impl DefaultLayoutEngine {
             .map(|item| {
                 let local_main = item.basis.unwrap_or_else(|| {
                     if unit.direction.is_horizontal() {
-                        Self::calc_unit_min_width(&item.slot)
+                        self.calc_unit_min_width(&item.slot)
                     } else {
-                        Self::calc_unit_min_height(&item.slot)
+                        self.calc_unit_min_height(&item.slot)
                     }
                 });
+                let local_main =
+                    Self::clamp_flex_item(item, local_main, unit.direction.is_horizontal());
                 let local_main = local_main
                     + if unit.direction.is_horizontal() {
                         item.margin.left + item.margin.right
@@ -294,9 +388,9 @@ impl DefaultLayoutEngine {
                         item.margin.top + item.margin.bottom
                     };
                 let local_cross = if unit.direction.is_horizontal() {
-                    Self::calc_unit_min_height(&item.slot)
+                    self.calc_unit_min_height(&item.slot)
                 } else {
-                    Self::calc_unit_min_width(&item.slot)
+                    self.calc_unit_min_width(&item.slot)
                 };
                 let local_cross = local_cross
                     + if unit.direction.is_horizontal() {
@@ -307,36 +401,40 @@ impl DefaultLayoutEngine {
                 let local_cross = lerp(local_cross, cross_available, item.fill);
                 main += local_main;
                 cross = cross.max(local_cross);
-                grow += item.grow;
-                shrink += item.shrink;
                 (local_main, local_cross)
             })
             .collect::<Vec<_>>();
-        main += items.len().checked_sub(1).unwrap_or(0) as Scalar * unit.separation;
-        let diff = main_available - main;
-        let mut new_main = 0.0;
+        let separations = items.len().saturating_sub(1) as Scalar * unit.separation;
+        main += separations;
+        let mains = Self::distribute_flex_items(
+            &items
+                .iter()
+                .zip(axis_sizes.iter())
+                .map(|(item, (local_main, _))| {
+                    let factor = if main < main_available {
+                        item.grow
+                    } else if main > main_available {
+                        item.shrink
+                    } else {
+                        0.0
+                    };
+                    (*item, *local_main, factor)
+                })
+                .collect::<Vec<_>>(),
+            main_available - separations,
+            unit.direction.is_horizontal(),
+        );
+        let diff = main_available - separations - mains.iter().sum::<Scalar>();
+        let (main_offset, main_spacing) =
+            unit.justify_content.offset_and_spacing(diff, items.len());
+        let main_trailing = Self::flex_trailing_space(unit.justify_content, diff, items.len());
+        let separation = unit.separation + main_spacing;
+        let mut new_main = main_offset;
         let mut new_cross: Scalar = 0.0;
         let children = items
             .into_iter()
-            .zip(axis_sizes.into_iter())
-            .filter_map(|(item, axis_size)| {
-                let child_main = if main < main_available {
-                    axis_size.0
-                        + if grow > 0.0 {
-                            diff * item.grow / grow
-                        } else {
-                            0.0
-                        }
-                } else if main > main_available {
-                    axis_size.0
-                        + if shrink > 0.0 {
-                            diff * item.shrink / shrink
-                        } else {
-                            0.0
-                        }
-                } else {
-                    axis_size.0
-                };
+            .zip(axis_sizes.into_iter().zip(mains))
+            .filter_map(|(item, (axis_size, child_main))| {
                 let child_main = (child_main
                     - if unit.direction.is_horizontal() {
                         item.margin.left + item.margin.right
@@ -351,6 +449,8 @@ impl DefaultLayoutEngine {
                         item.margin.left + item.margin.right
                     })
                 .max(0.0);
+                let child_cross =
+                    Self::clamp_flex_item(item, child_cross, unit.direction.is_vertical());
                 let rect = if unit.direction.is_horizontal() {
                     Vec2 {
                         x: child_main,
@@ -362,7 +462,7 @@ impl DefaultLayoutEngine {
                         y: child_main,
                     }
                 };
-                if let Some(mut child) = Self::layout_node(rect, &item.slot) {
+                if let Some(mut child) = self.layout_node(rect, &item.slot) {
                     if unit.direction.is_horizontal() {
                         if unit.direction.is_order_ascending() {
                             child.local_space.left += new_main + item.margin.left;
@@ -403,14 +503,14 @@ impl DefaultLayoutEngine {
                         child.local_space.right += item.margin.left + diff;
                         new_cross = new_cross.max(rect.x);
                     }
-                    new_main += unit.separation;
+                    new_main += separation;
                     Some(child)
                 } else {
                     None
                 }
             })
             .collect::<Vec<_>>();
-        new_main = (new_main - unit.separation).max(0.0);
+        new_main = (new_main - separation).max(0.0) + main_trailing;
         let local_space = if unit.direction.is_horizontal() {
             Rect {
                 left: 0.0,
@@ -433,28 +533,121 @@ impl DefaultLayoutEngine {
         }
     }
 
-    pub fn layout_grid_box(size_available: Vec2, unit: &GridBox) -> Option<LayoutNode> {
-        if unit.cols == 0 || unit.rows == 0 {
+    fn flex_trailing_space(
+        distribution: FlexBoxDistribution,
+        free_space: Scalar,
+        count: usize,
+    ) -> Scalar {
+        if count == 0 {
+            return 0.0;
+        }
+        let (offset, spacing) = distribution.offset_and_spacing(free_space, count);
+        (free_space.max(0.0) - offset - spacing * (count - 1) as Scalar).max(0.0)
+    }
+
+    fn clamp_flex_item(item: &FlexBoxItem, value: Scalar, horizontal: bool) -> Scalar {
+        let value = match &item.slot {
+            WidgetUnit::SizeBox(b) if horizontal => b.clamp_outer_width(value),
+            WidgetUnit::SizeBox(b) => b.clamp_outer_height(value),
+            _ => value,
+        };
+        if horizontal {
+            item.clamp_width(value)
+        } else {
+            item.clamp_height(value)
+        }
+    }
+
+    fn distribute_flex_items(
+        items: &[(&FlexBoxItem, Scalar, Scalar)],
+        main_available: Scalar,
+        horizontal: bool,
+    ) -> Vec<Scalar> {
+        let clamp = |item: &FlexBoxItem, value: Scalar| {
+            let margin = if horizontal {
+                item.margin.left + item.margin.right
+            } else {
+                item.margin.top + item.margin.bottom
+            };
+            Self::clamp_flex_item(item, value - margin, horizontal) + margin
+        };
+        let mut sizes = items.iter().map(|(_, size, _)| *size).collect::<Vec<_>>();
+        let mut frozen = items
+            .iter()
+            .map(|(_, _, factor)| *factor <= 0.0)
+            .collect::<Vec<_>>();
+        loop {
+            let factors = items
+                .iter()
+                .zip(frozen.iter())
+                .filter(|(_, frozen)| !**frozen)
+                .map(|((_, _, factor), _)| *factor)
+                .sum::<Scalar>();
+            if factors <= 0.0 {
+                break;
+            }
+            let used = items
+                .iter()
+                .zip(sizes.iter())
+                .zip(frozen.iter())
+                .map(|(((_, size, _), current), frozen)| if *frozen { *current } else { *size })
+                .sum::<Scalar>();
+            let diff = main_available - used;
+            let mut changed = false;
+            for (((item, size, factor), current), frozen) in items
+                .iter()
+                .zip(sizes.iter_mut())
+                .zip(frozen.iter_mut())
+                .filter(|(_, frozen)| !**frozen)
+            {
+                let value = size + diff * factor / factors;
+                *current = clamp(item, value);
+                if (*current - value).abs() > 1.0e-4 {
+                    *frozen = true;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        sizes
+    }
+
+    pub fn layout_grid_box(&self, size_available: Vec2, unit: &GridBox) -> Option<LayoutNode> {
+        let occupancies = unit.space_occupancies();
+        let (cols, rows) = match unit.auto_flow {
+            GridBoxAutoFlow::None => (unit.cols_count(), unit.rows_count()),
+            _ => occupancies.iter().fold(
+                (unit.cols_count(), unit.rows_count()),
+                |(cols, rows), rect| {
+                    (
+                        cols.max(rect.right as usize),
+                        rows.max(rect.bottom as usize),
+                    )
+                },
+            ),
+        };
+        if cols == 0 || rows == 0 {
             return None;
         }
 
-        let cell_width = size_available.x / unit.cols as Scalar;
-        let cell_height = size_available.y / unit.rows as Scalar;
+        let col_tracks = self.layout_grid_tracks(size_available.x, cols, unit, &occupancies, true);
+        let row_tracks = self.layout_grid_tracks(size_available.y, rows, unit, &occupancies, false);
         let children = unit
             .items
             .iter()
-            .filter_map(|item| {
-                let left = item.space_occupancy.left as Scalar * cell_width;
-                let right = item.space_occupancy.right as Scalar * cell_width;
-                let top = item.space_occupancy.top as Scalar * cell_height;
-                let bottom = item.space_occupancy.bottom as Scalar * cell_height;
+            .zip(occupancies.iter())
+            .filter_map(|(item, occupancy)| {
+                let (left, right) = Self::grid_span(&col_tracks, occupancy.left, occupancy.right);
+                let (top, bottom) = Self::grid_span(&row_tracks, occupancy.top, occupancy.bottom);
                 let width = (right - left - item.margin.left - item.margin.right).max(0.0);
                 let height = (bottom - top - item.margin.top - item.margin.bottom).max(0.0);
                 let size = Vec2 {
                     x: width,
                     y: height,
                 };
-                if let Some(mut child) = Self::layout_node(size, &item.slot) {
+                if let Some(mut child) = self.layout_node(size, &item.slot) {
                     let diff = size.x - child.local_space.width();
                     let ox = lerp(0.0, diff, item.horizontal_align);
                     let diff = size.y - child.local_space.height();
@@ -481,12 +674,140 @@ impl DefaultLayoutEngine {
         })
     }
 
-    pub fn layout_size_box(size_available: Vec2, unit: &SizeBox) -> LayoutNode {
+    fn layout_grid_tracks(
+        &self,
+        length_available: Scalar,
+        count: usize,
+        unit: &GridBox,
+        occupancies: &[IntRect],
+        horizontal: bool,
+    ) -> Vec<(Scalar, Scalar)> {
+        let (tracks, gap) = if horizontal {
+            (&unit.col_tracks, unit.col_gap)
+        } else {
+            (&unit.row_tracks, unit.row_gap)
+        };
+        let tracks = (0..count)
+            .map(|index| tracks.get(index).copied().unwrap_or_default())
+            .collect::<Vec<_>>();
+        let space = (length_available - gap * count.saturating_sub(1) as Scalar).max(0.0);
+        let mut sizes = tracks
+            .iter()
+            .enumerate()
+            .map(|(index, track)| match track.size {
+                GridBoxTrackSize::Exact(v) => track.clamp(v),
+                GridBoxTrackSize::Fraction(_) => 0.0,
+                GridBoxTrackSize::Content => {
+                    let size = unit
+                        .items
+                        .iter()
+                        .zip(occupancies.iter())
+                        .filter(|(_, occupancy)| {
+                            let (from, to) = if horizontal {
+                                (occupancy.left, occupancy.right)
+                            } else {
+                                (occupancy.top, occupancy.bottom)
+                            };
+                            from == index as Integer && to == from + 1
+                        })
+                        .map(|(item, _)| {
+                            if horizontal {
+                                self.calc_unit_min_width(&item.slot)
+                                    + item.margin.left
+                                    + item.margin.right
+                            } else {
+                                self.calc_unit_min_height(&item.slot)
+                                    + item.margin.top
+                                    + item.margin.bottom
+                            }
+                        })
+                        .fold(0.0, Scalar::max);
+                    track.clamp(size)
+                }
+            })
+            .collect::<Vec<_>>();
+        let mut frozen = tracks
+            .iter()
+            .map(|track| !matches!(track.size, GridBoxTrackSize::Fraction(_)))
+            .collect::<Vec<_>>();
+        loop {
+            let fractions = tracks
+                .iter()
+                .zip(frozen.iter())
+                .filter(|(_, frozen)| !**frozen)
+                .map(|(track, _)| match track.size {
+                    GridBoxTrackSize::Fraction(v) => v.max(0.0),
+                    _ => 0.0,
+                })
+                .sum::<Scalar>();
+            let used = sizes
+                .iter()
+                .zip(frozen.iter())
+                .filter(|(_, frozen)| **frozen)
+                .map(|(size, _)| *size)
+                .sum::<Scalar>();
+            let remaining = (space - used).max(0.0);
+            let mut changed = false;
+            for ((size, track), frozen) in sizes
+                .iter_mut()
+                .zip(tracks.iter())
+                .zip(frozen.iter_mut())
+                .filter(|(_, frozen)| !**frozen)
+            {
+                if let GridBoxTrackSize::Fraction(v) = track.size {
+                    let value = if fractions > 0.0 {
+                        remaining * v.max(0.0) / fractions
+                    } else {
+                        0.0
+                    };
+                    *size = track.clamp(value);
+                    if (*size - value).abs() > 1.0e-4 {
+                        *frozen = true;
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        let mut position = 0.0;
+        sizes
+            .into_iter()
+            .map(|size| {
+                let result = (position, size);
+                position += size + gap;
+                result
+            })
+            .collect()
+    }
+
+    fn grid_span(tracks: &[(Scalar, Scalar)], from: Integer, to: Integer) -> (Scalar, Scalar) {
+        let count = tracks.len() as Integer;
+        let from = from.clamp(0, count);
+        let to = to.clamp(from, count);
+        if from == to {
+            let position = match tracks.get(from as usize) {
+                Some((position, _)) => *position,
+                None => tracks
+                    .last()
+                    .map(|(position, size)| position + size)
+                    .unwrap_or_default(),
+            };
+            return (position, position);
+        }
+        let (start, _) = tracks[from as usize];
+        let (position, size) = tracks[to as usize - 1];
+        (start, position + size)
+    }
+
+    pub fn layout_size_box(&self, size_available: Vec2, unit: &SizeBox) -> LayoutNode {
         let size = Vec2 {
-            x: (size_available.x - unit.margin.left - unit.margin.right).max(0.0),
-            y: (size_available.y - unit.margin.top - unit.margin.bottom).max(0.0),
+            x: unit.clamp_width((size_available.x - unit.margin.left - unit.margin.right).max(0.0)),
+            y: unit
+                .clamp_height((size_available.y - unit.margin.top - unit.margin.bottom).max(0.0)),
         };
-        let (size, children) = if let Some(mut child) = Self::layout_node(size, &unit.slot) {
+        let (size, children) = if let Some(mut child) = self.layout_node(size, &unit.slot) {
             child.local_space.left += unit.margin.left;
             child.local_space.right += unit.margin.left;
             child.local_space.top += unit.margin.top;
@@ -498,15 +819,15 @@ impl DefaultLayoutEngine {
         let local_space = Rect {
             left: 0.0,
             right: match unit.width {
-                SizeBoxSizeValue::Content => size.x,
-                SizeBoxSizeValue::Fill => size_available.x,
-                SizeBoxSizeValue::Exact(v) => v,
+                SizeBoxSizeValue::Content => unit.clamp_width(size.x),
+                SizeBoxSizeValue::Fill => unit.clamp_outer_width(size_available.x),
+                SizeBoxSizeValue::Exact(v) => unit.clamp_outer_width(v),
             },
             top: 0.0,
             bottom: match unit.height {
-                SizeBoxSizeValue::Content => size.y,
-                SizeBoxSizeValue::Fill => size_available.y,
-                SizeBoxSizeValue::Exact(v) => v,
+                SizeBoxSizeValue::Content => unit.clamp_height(size.y),
+                SizeBoxSizeValue::Fill => unit.clamp_outer_height(size_available.y),
+                SizeBoxSizeValue::Exact(v) => unit.clamp_outer_height(v),
             },
         };
         LayoutNode {
@@ -516,38 +837,88 @@ impl DefaultLayoutEngine {
         }
     }
 
-    pub fn layout_image_box(size_available: Vec2, unit: &ImageBox) -> LayoutNode {
-        let local_space = Rect {
-            left: 0.0,
-            right: match unit.width {
-                ImageBoxSizeValue::Fill => size_available.x,
-                ImageBoxSizeValue::Exact(v) => v,
-            },
-            top: 0.0,
-            bottom: match unit.height {
-                ImageBoxSizeValue::Fill => size_available.y,
-                ImageBoxSizeValue::Exact(v) => v,
-            },
+    pub fn layout_image_box(&self, size_available: Vec2, unit: &ImageBox) -> LayoutNode {
+        let image_size = self.image_size(unit);
+        let width = match unit.width {
+            ImageBoxSizeValue::Content => image_size.map(|size| size.x).unwrap_or_default(),
+            ImageBoxSizeValue::Fill => size_available.x,
+            ImageBoxSizeValue::Exact(v) => v,
+        };
+        let height = match unit.height {
+            ImageBoxSizeValue::Content => image_size.map(|size| size.y).unwrap_or_default(),
+            ImageBoxSizeValue::Fill => size_available.y,
+            ImageBoxSizeValue::Exact(v) => v,
         };
         LayoutNode {
             id: unit.id.to_owned(),
-            local_space,
+            local_space: Rect {
+                left: 0.0,
+                right: width,
+                top: 0.0,
+                bottom: height,
+            },
             children: vec![],
         }
     }
 
-    pub fn layout_text_box(size_available: Vec2, unit: &TextBox) -> LayoutNode {
+    fn fit_image_boxes(&self, unit: &WidgetUnit, items: &mut HashMap<WidgetId, LayoutItem>) {
+        if let WidgetUnit::ImageBox(b) = unit {
+            if let (Some(aspect), Some(size), Some(item)) = (
+                b.content_keep_aspect_ratio,
+                self.image_size(b),
+                items.get_mut(&b.id),
+            ) {
+                if size.x > 0.0 && size.y > 0.0 {
+                    let width = item.ui_space.width();
+                    let height = item.ui_space.height();
+                    let scale = (width / size.x).min(height / size.y);
+                    let w = size.x * scale;
+                    let h = size.y * scale;
+                    let ox = lerp(0.0, width - w, aspect.horizontal_alignment);
+                    let oy = lerp(0.0, height - h, aspect.vertical_alignment);
+                    item.local_space.left += ox;
+                    item.local_space.right = item.local_space.left + w;
+                    item.local_space.top += oy;
+                    item.local_space.bottom = item.local_space.top + h;
+                    item.ui_space.left += ox;
+                    item.ui_space.right = item.ui_space.left + w;
+                    item.ui_space.top += oy;
+                    item.ui_space.bottom = item.ui_space.top + h;
+                }
+            }
+        }
+        if let Some(data) = unit.as_data() {
+            for child in data.get_children() {
+                self.fit_image_boxes(child, items);
+            }
+        }
+    }
+
+    pub fn layout_text_box(&self, size_available: Vec2, unit: &TextBox) -> LayoutNode {
+        let width = match unit.width {
+            TextBoxSizeValue::Content => {
+                let max_width = Some(size_available.x);
+                self.text_measurer
+                    .measure_text(&unit.text, &unit.font, max_width)
+                    .x
+            }
+            TextBoxSizeValue::Fill => size_available.x,
+            TextBoxSizeValue::Exact(v) => v,
+        };
+        let height = match unit.height {
+            TextBoxSizeValue::Content => {
+                self.text_measurer
+                    .measure_text(&unit.text, &unit.font, Some(width))
+                    .y
+            }
+            TextBoxSizeValue::Fill => size_available.y,
+            TextBoxSizeValue::Exact(v) => v,
+        };
         let local_space = Rect {
             left: 0.0,
-            right: match unit.width {
-                TextBoxSizeValue::Fill => size_available.x,
-                TextBoxSizeValue::Exact(v) => v,
-            },
+            right: width,
             top: 0.0,
-            bottom: match unit.height {
-                TextBoxSizeValue::Fill => size_available.y,
-                TextBoxSizeValue::Exact(v) => v,
-            },
+            bottom: height,
         };
         LayoutNode {
             id: unit.id.to_owned(),
@@ -556,52 +927,77 @@ impl DefaultLayoutEngine {
         }
     }
 
-    fn calc_unit_min_width(unit: &WidgetUnit) -> Scalar {
+    fn calc_unit_min_width(&self, unit: &WidgetUnit) -> Scalar {
         match unit {
             WidgetUnit::ImageBox(b) => match b.width {
+                ImageBoxSizeValue::Content => {
+                    self.image_size(b).map(|size| size.x).unwrap_or_default()
+                }
                 ImageBoxSizeValue::Fill => 0.0,
                 ImageBoxSizeValue::Exact(v) => v,
             },
             WidgetUnit::TextBox(b) => match b.width {
+                TextBoxSizeValue::Content => {
+                    self.text_measurer.measure_text(&b.text, &b.font, None).x
+                }
                 TextBoxSizeValue::Fill => 0.0,
                 TextBoxSizeValue::Exact(v) => v,
             },
             WidgetUnit::SizeBox(b) => {
-                b.margin.top
-                    + b.margin.bottom
-                    + match b.width {
-                        SizeBoxSizeValue::Content => Self::calc_unit_min_width(&b.slot),
+                b.margin.left
+                    + b.margin.right
+                    + b.clamp_width(match b.width {
+                        SizeBoxSizeValue::Content => self.calc_unit_min_width(&b.slot),
                         SizeBoxSizeValue::Fill => 0.0,
                         SizeBoxSizeValue::Exact(v) => v,
-                    }
+                    })
             }
             _ => 0.0,
         }
     }
 
-    fn calc_unit_min_height(unit: &WidgetUnit) -> Scalar {
+    fn calc_unit_min_height(&self, unit: &WidgetUnit) -> Scalar {
         match unit {
             WidgetUnit::ImageBox(b) => match b.height {
+                ImageBoxSizeValue::Content => {
+                    self.image_size(b).map(|size| size.y).unwrap_or_default()
+                }
                 ImageBoxSizeValue::Fill => 0.0,
                 ImageBoxSizeValue::Exact(v) => v,
             },
             WidgetUnit::TextBox(b) => match b.height {
+                TextBoxSizeValue::Content => {
+                    let max_width = match b.width {
+                        TextBoxSizeValue::Exact(v) => Some(v),
+                        _ => None,
+                    };
+                    self.text_measurer
+                        .measure_text(&b.text, &b.font, max_width)
+                        .y
+                }
                 TextBoxSizeValue::Fill => 0.0,
                 TextBoxSizeValue::Exact(v) => v,
             },
             WidgetUnit::SizeBox(b) => {
                 b.margin.top
                     + b.margin.bottom
-                    + match b.height {
-                        SizeBoxSizeValue::Content => Self::calc_unit_min_height(&b.slot),
+                    + b.clamp_height(match b.height {
+                        SizeBoxSizeValue::Content => self.calc_unit_min_height(&b.slot),
                         SizeBoxSizeValue::Fill => 0.0,
                         SizeBoxSizeValue::Exact(v) => v,
-                    }
+                    })
             }
             _ => 0.0,
         }
     }
 
+    fn image_size(&self, unit: &ImageBox) -> Option<Vec2> {
+        match &unit.material {
+            ImageBoxMaterial::Image(image) => self.image_size_provider.image_size(&image.id),
+            _ => None,
+        }
+    }
+
     fn unpack_node(ui_space: Rect, node: LayoutNode, items: &mut HashMap<WidgetId, LayoutItem>) {
         let LayoutNode {
             id,
@@ -627,11 +1023,16 @@ impl DefaultLayoutEngine {
     }
 }
 
-impl LayoutEngine<()> for DefaultLayoutEngine {
+impl<TM, IS> LayoutEngine<()> for DefaultLayoutEngine<TM, IS>
+where
+    TM: TextMeasurer,
+    IS: ImageSizeProvider,
+{
     fn layout(&mut self, ui_space: Rect, tree: &WidgetUnit) -> Result<Layout, ()> {
-        if let Some(root) = Self::layout_node(ui_space.size(), tree) {
+        if let Some(root) = self.layout_node(ui_space.size(), tree) {
             let mut items = HashMap::with_capacity(root.count());
             Self::unpack_node(ui_space, root, &mut items);
+            self.fit_image_boxes(tree, &mut items);
             Ok(Layout { ui_space, items })
         } else {
             Ok(Layout {
@@ -0,0 +1,27 @@
+use crate::widget::utils::Vec2;
+use std::collections::HashMap;
+
+pub trait ImageSizeProvider {
+    fn image_size(&self, id: &str) -> Option<Vec2>;
+}
+
+impl ImageSizeProvider for () {
+    fn image_size(&self, _: &str) -> Option<Vec2> {
+        None
+    }
+}
+
+impl ImageSizeProvider for HashMap<String, Vec2> {
+    fn image_size(&self, id: &str) -> Option<Vec2> {
+        self.get(id).copied()
+    }
+}
+
+impl<T> ImageSizeProvider for &T
+where
+    T: ImageSizeProvider,
+{
+    fn image_size(&self, id: &str) -> Option<Vec2> {
+        (*self).image_size(id)
+    }
+}
@@ -1,19 +1,22 @@
 pub mod default_layout_engine;
+pub mod image_size_provider;
+pub mod text_measurer;
 
 use crate::widget::{unit::WidgetUnit, utils::Rect, WidgetId};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 pub trait LayoutEngine<E> {
     fn layout(&mut self, ui_space: Rect, tree: &WidgetUnit) -> Result<Layout, E>;
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Layout {
     pub ui_space: Rect,
     pub items: HashMap<WidgetId, LayoutItem>,
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LayoutNode {
     pub id: WidgetId,
     pub local_space: Rect,
@@ -26,7 +29,7 @@ impl LayoutNode {
     }
 }
 
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LayoutItem {
     pub local_space: Rect,
     pub ui_space: Rect,
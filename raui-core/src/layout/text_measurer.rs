@@ -0,0 +1,105 @@
+use crate::{
+    widget::{unit::text::TextBoxFont, utils::Vec2},
+    Scalar,
+};
+
+pub trait TextMeasurer {
+    fn measure_text(&self, text: &str, font: &TextBoxFont, max_width: Option<Scalar>) -> Vec2;
+}
+
+impl TextMeasurer for () {
+    fn measure_text(&self, _: &str, _: &TextBoxFont, _: Option<Scalar>) -> Vec2 {
+        Vec2::default()
+    }
+}
+
+#[cfg(feature = "ab_glyph")]
+pub use self::ab_glyph_measurer::*;
+
+#[cfg(feature = "ab_glyph")]
+mod ab_glyph_measurer {
+    use super::TextMeasurer;
+    use crate::{
+        widget::{unit::text::TextBoxFont, utils::Vec2},
+        Scalar,
+    };
+    use ab_glyph::{Font, FontArc, InvalidFont, PxScale, ScaleFont};
+    use std::collections::HashMap;
+
+    #[derive(Debug, Default, Clone)]
+    pub struct AbGlyphTextMeasurer {
+        fonts: HashMap<String, FontArc>,
+    }
+
+    impl AbGlyphTextMeasurer {
+        pub fn add_font(&mut self, name: &str, font: FontArc) {
+            self.fonts.insert(name.to_owned(), font);
+        }
+
+        pub fn load_font(&mut self, name: &str, bytes: Vec<u8>) -> Result<(), InvalidFont> {
+            self.add_font(name, FontArc::try_from_vec(bytes)?);
+            Ok(())
+        }
+
+        pub fn remove_font(&mut self, name: &str) {
+            self.fonts.remove(name);
+        }
+
+        pub fn has_font(&self, name: &str) -> bool {
+            self.fonts.contains_key(name)
+        }
+    }
+
+    impl TextMeasurer for AbGlyphTextMeasurer {
+        fn measure_text(&self, text: &str, font: &TextBoxFont, max_width: Option<Scalar>) -> Vec2 {
+            let scaled = match self.fonts.get(&font.name) {
+                Some(item) => item.as_scaled(PxScale::from(font.size)),
+                None => return Vec2::default(),
+            };
+            let word_width = |word: &str| {
+                let mut result = 0.0;
+                let mut previous = None;
+                for c in word.chars() {
+                    let glyph = scaled.glyph_id(c);
+                    if let Some(previous) = previous {
+                        result += scaled.kern(previous, glyph);
+                    }
+                    result += scaled.h_advance(glyph);
+                    previous = Some(glyph);
+                }
+                result
+            };
+            let space = scaled.h_advance(scaled.glyph_id(' '));
+            let mut width: Scalar = 0.0;
+            let mut lines = 0;
+            for paragraph in text.split('\n') {
+                let mut line: Option<Scalar> = None;
+                for word in paragraph.split(' ') {
+                    let size = word_width(word);
+                    line = Some(match line {
+                        Some(current) => {
+                            if max_width
+                                .map(|max| current + space + size > max)
+                                .unwrap_or(false)
+                            {
+                                width = width.max(current);
+                                lines += 1;
+                                size
+                            } else {
+                                current + space + size
+                            }
+                        }
+                        None => size,
+                    });
+                }
+                width = width.max(line.unwrap_or_default());
+                lines += 1;
+            }
+            let line_height = scaled.height() + scaled.line_gap();
+            Vec2 {
+                x: width,
+                y: (line_height * lines as Scalar - scaled.line_gap()).max(0.0),
+            }
+        }
+    }
+}
@@ -1,36 +1,70 @@
+pub mod animator;
 pub mod application;
+pub mod diff;
+pub mod hooks;
+pub mod interactions;
 pub mod messenger;
 #[macro_use]
 pub mod props;
+pub mod registry;
 pub mod renderer;
 pub mod state;
+pub mod store;
+pub mod tasks;
+pub mod timers;
 #[macro_use]
 pub mod widget;
 pub mod layout;
+pub mod loader;
 pub mod signals;
+pub mod snapshot;
 
 pub type Scalar = f32;
 pub type Integer = i32;
 
+#[cfg(not(feature = "sync"))]
+pub trait MaybeSend {}
+#[cfg(not(feature = "sync"))]
+impl<T> MaybeSend for T {}
+
+#[cfg(feature = "sync")]
+pub trait MaybeSend: Send {}
+#[cfg(feature = "sync")]
+impl<T> MaybeSend for T where T: Send {}
+
 pub mod prelude {
     pub use crate::{
+        animator::*,
         application::*,
+        diff::*,
+        hooks::*,
+        interactions::default_interactions_engine::*,
+        interactions::focus::*,
+        interactions::*,
         layout::default_layout_engine::*,
+        layout::image_size_provider::*,
+        layout::text_measurer::*,
         layout::*,
+        loader::*,
         messenger::*,
         props::*,
+        registry::*,
         renderer::*,
         signals::*,
+        snapshot::*,
         state::*,
+        store::*,
+        tasks::*,
+        timers::*,
         widget::*,
         widget::{
-            component::*,
+            component::{error_boundary::*, *},
             context::*,
             node::*,
             unit::*,
             unit::{content::*, flex::*, grid::*, image::*, size::*, text::*},
             utils::*,
         },
-        Integer, Scalar,
+        Integer, MaybeSend, Scalar,
     };
 }
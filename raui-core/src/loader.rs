@@ -0,0 +1,59 @@
+use crate::widget::unit::WidgetUnit;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LoaderErrorPosition {
+    LineColumn { line: usize, column: usize },
+    Offset(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoaderError {
+    pub message: String,
+    pub position: Option<LoaderErrorPosition>,
+}
+
+impl LoaderError {
+    pub fn new(message: String, position: Option<LoaderErrorPosition>) -> Self {
+        Self { message, position }
+    }
+
+    pub fn at_line_column(message: String, line: usize, column: usize) -> Self {
+        Self::new(
+            message,
+            Some(LoaderErrorPosition::LineColumn { line, column }),
+        )
+    }
+
+    pub fn at_offset(message: String, offset: usize) -> Self {
+        Self::new(message, Some(LoaderErrorPosition::Offset(offset)))
+    }
+}
+
+impl std::fmt::Display for LoaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.position {
+            Some(LoaderErrorPosition::LineColumn { line, column }) => {
+                write!(f, "{} at line {} column {}", self.message, line, column)
+            }
+            Some(LoaderErrorPosition::Offset(offset)) => {
+                write!(f, "{} at offset {}", self.message, offset)
+            }
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl std::error::Error for LoaderError {}
+
+pub trait Loader<T, E> {
+    fn load(&mut self, source: T) -> Result<WidgetUnit, E>;
+}
+
+#[derive(Debug, Default, Copy, Clone)]
+pub struct RawLoader;
+
+impl Loader<WidgetUnit, ()> for RawLoader {
+    fn load(&mut self, source: WidgetUnit) -> Result<WidgetUnit, ()> {
+        Ok(source)
+    }
+}
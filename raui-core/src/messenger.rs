@@ -1,11 +1,14 @@
-use crate::widget::WidgetId;
+use crate::{widget::WidgetId, MaybeSend};
 use std::{
     any::Any,
     collections::HashMap,
     sync::mpsc::{Receiver, Sender},
 };
 
+#[cfg(not(feature = "sync"))]
 pub type Message = Box<dyn Any>;
+#[cfg(feature = "sync")]
+pub type Message = Box<dyn Any + Send>;
 pub type Messages = Vec<Message>;
 
 pub struct MessageReceiver(Receiver<(WidgetId, Message)>);
@@ -64,7 +67,7 @@ impl<'a> Messenger<'a> {
 
     pub fn write<T>(&self, id: WidgetId, message: T) -> bool
     where
-        T: 'static,
+        T: 'static + MaybeSend,
     {
         self.sender.write(id, Box::new(message))
     }
@@ -1,4 +1,8 @@
-use std::{any::Any, borrow::Cow};
+use std::{
+    any::{Any, TypeId},
+    borrow::Cow,
+    collections::HashMap,
+};
 
 pub enum PropsError {
     CouldNotReadData,
@@ -7,6 +11,10 @@ pub enum PropsError {
 pub trait PropsData: Any {
     fn clone_props(&self) -> Box<dyn PropsData>;
     fn as_any(&self) -> &dyn Any;
+
+    fn props_eq(&self, _other: &dyn PropsData) -> bool {
+        false
+    }
 }
 
 impl Clone for Box<dyn PropsData> {
@@ -38,6 +46,10 @@ impl Props {
         Self(Box::new(data))
     }
 
+    pub fn props_eq(&self, other: &Self) -> bool {
+        self.0.props_eq(other.0.as_ref())
+    }
+
     pub fn is<T>(&self) -> bool
     where
         T: 'static + PropsData,
@@ -75,6 +87,63 @@ impl Props {
     }
 }
 
+#[derive(Default, Clone)]
+pub struct SharedProps(HashMap<TypeId, Props>);
+
+impl std::fmt::Debug for SharedProps {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SharedProps {...}")
+    }
+}
+
+impl SharedProps {
+    pub fn provide(&mut self, props: Props) {
+        self.0.insert(props.0.as_any().type_id(), props);
+    }
+
+    pub fn props_eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len()
+            && self
+                .0
+                .iter()
+                .all(|(type_id, props)| match other.0.get(type_id) {
+                    Some(other) => props.props_eq(other),
+                    None => false,
+                })
+    }
+
+    pub fn has<T>(&self) -> bool
+    where
+        T: 'static + PropsData,
+    {
+        self.0.contains_key(&TypeId::of::<T>())
+    }
+
+    pub fn read<T>(&self) -> Result<&T, PropsError>
+    where
+        T: 'static + PropsData,
+    {
+        match self.0.get(&TypeId::of::<T>()) {
+            Some(props) => props.read(),
+            None => Err(PropsError::CouldNotReadData),
+        }
+    }
+
+    pub fn read_cloned<T>(&self) -> Result<T, PropsError>
+    where
+        T: 'static + PropsData + Clone,
+    {
+        self.read::<T>().cloned()
+    }
+
+    pub fn read_cloned_or_default<T>(&self) -> T
+    where
+        T: 'static + PropsData + Clone + Default,
+    {
+        self.read_cloned().unwrap_or_default()
+    }
+}
+
 impl<T> From<T> for Props
 where
     T: 'static + PropsData,
@@ -106,23 +175,44 @@ macro_rules! implement_props_data {
             }
         }
     };
+    ($type_name:ty, PartialEq) => {
+        impl $crate::props::PropsData for $type_name
+        where
+            Self: Clone + PartialEq,
+        {
+            fn clone_props(&self) -> Box<dyn $crate::props::PropsData> {
+                Box::new(self.clone())
+            }
+
+            fn as_any(&self) -> &dyn std::any::Any {
+                self
+            }
+
+            fn props_eq(&self, other: &dyn $crate::props::PropsData) -> bool {
+                match other.as_any().downcast_ref::<Self>() {
+                    Some(other) => self == other,
+                    None => false,
+                }
+            }
+        }
+    };
 }
 
-implement_props_data!(());
-implement_props_data!(i8);
-implement_props_data!(i16);
-implement_props_data!(i32);
-implement_props_data!(i64);
-implement_props_data!(i128);
-implement_props_data!(u8);
-implement_props_data!(u16);
-implement_props_data!(u32);
-implement_props_data!(u64);
-implement_props_data!(u128);
-implement_props_data!(f32);
-implement_props_data!(f64);
-implement_props_data!(bool);
-implement_props_data!(String);
+implement_props_data!((), PartialEq);
+implement_props_data!(i8, PartialEq);
+implement_props_data!(i16, PartialEq);
+implement_props_data!(i32, PartialEq);
+implement_props_data!(i64, PartialEq);
+implement_props_data!(i128, PartialEq);
+implement_props_data!(u8, PartialEq);
+implement_props_data!(u16, PartialEq);
+implement_props_data!(u32, PartialEq);
+implement_props_data!(u64, PartialEq);
+implement_props_data!(u128, PartialEq);
+implement_props_data!(f32, PartialEq);
+implement_props_data!(f64, PartialEq);
+implement_props_data!(bool, PartialEq);
+implement_props_data!(String, PartialEq);
 
 impl<T> PropsData for Option<T>
 where
@@ -135,6 +225,14 @@ where
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn props_eq(&self, other: &dyn PropsData) -> bool {
+        match (self, other.as_any().downcast_ref::<Self>()) {
+            (Some(a), Some(Some(b))) => a.props_eq(b),
+            (None, Some(None)) => true,
+            _ => false,
+        }
+    }
 }
 
 impl<T, E> PropsData for Result<T, E>
@@ -149,6 +247,13 @@ where
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn props_eq(&self, other: &dyn PropsData) -> bool {
+        match (self, other.as_any().downcast_ref::<Self>()) {
+            (Ok(a), Some(Ok(b))) => a.props_eq(b),
+            _ => false,
+        }
+    }
 }
 
 impl<T> PropsData for Cow<'static, T>
@@ -162,6 +267,13 @@ where
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn props_eq(&self, other: &dyn PropsData) -> bool {
+        match other.as_any().downcast_ref::<Self>() {
+            Some(other) => self.as_ref().props_eq(other.as_ref()),
+            None => false,
+        }
+    }
 }
 
 impl<T> PropsData for Box<T>
@@ -175,4 +287,11 @@ where
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn props_eq(&self, other: &dyn PropsData) -> bool {
+        match other.as_any().downcast_ref::<Self>() {
+            Some(other) => self.as_ref().props_eq(other.as_ref()),
+            None => false,
+        }
+    }
 }
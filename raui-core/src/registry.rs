@@ -0,0 +1,197 @@
+use crate::{
+    props::{Props, PropsData},
+    widget::{component::WidgetComponent, node::WidgetNode, unit::WidgetUnit, FnWidget},
+};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use serde_value::Value;
+use std::{
+    any::type_name,
+    collections::{BTreeMap, HashMap},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComponentRegistryError {
+    CouldNotDeserializeNode(String),
+    UnknownComponent(String),
+    UnknownProps(String),
+    CouldNotDeserializeProps { type_name: String, message: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PropsDefinition {
+    pub type_name: String,
+    pub data: Value,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WidgetComponentDefinition {
+    pub type_name: String,
+    #[serde(default)]
+    pub key: Option<String>,
+    #[serde(default)]
+    pub props: Option<PropsDefinition>,
+    #[serde(default)]
+    pub shared_props: Option<PropsDefinition>,
+    #[serde(default)]
+    pub listed_slots: Vec<WidgetNodeDefinition>,
+    #[serde(default)]
+    pub named_slots: BTreeMap<String, WidgetNodeDefinition>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum WidgetNodeDefinition {
+    None,
+    Component(WidgetComponentDefinition),
+    Unit(WidgetUnit),
+}
+
+impl Default for WidgetNodeDefinition {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+type PropsDeserializer = fn(Value) -> Result<Props, String>;
+
+fn deserialize_props<T>(value: Value) -> Result<Props, String>
+where
+    T: 'static + PropsData + DeserializeOwned,
+{
+    T::deserialize(value)
+        .map(Props::new)
+        .map_err(|error| error.to_string())
+}
+
+#[derive(Default)]
+pub struct ComponentRegistry {
+    components: HashMap<String, FnWidget>,
+    props: HashMap<String, PropsDeserializer>,
+}
+
+impl std::fmt::Debug for ComponentRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ComponentRegistry")
+            .field("components", &self.components.keys())
+            .field("props", &self.props.keys())
+            .finish()
+    }
+}
+
+impl ComponentRegistry {
+    pub fn register_component(&mut self, name: &str, processor: FnWidget) {
+        self.components.insert(name.to_owned(), processor);
+    }
+
+    pub fn unregister_component(&mut self, name: &str) {
+        self.components.remove(name);
+    }
+
+    pub fn has_component(&self, name: &str) -> bool {
+        self.components.contains_key(name)
+    }
+
+    pub fn register_props<T>(&mut self)
+    where
+        T: 'static + PropsData + DeserializeOwned,
+    {
+        self.register_props_named::<T>(type_name::<T>());
+    }
+
+    pub fn register_props_named<T>(&mut self, name: &str)
+    where
+        T: 'static + PropsData + DeserializeOwned,
+    {
+        self.props.insert(name.to_owned(), deserialize_props::<T>);
+    }
+
+    pub fn unregister_props(&mut self, name: &str) {
+        self.props.remove(name);
+    }
+
+    pub fn has_props(&self, name: &str) -> bool {
+        self.props.contains_key(name)
+    }
+
+    pub fn build_props(
+        &self,
+        definition: PropsDefinition,
+    ) -> Result<Props, ComponentRegistryError> {
+        let PropsDefinition { type_name, data } = definition;
+        let deserializer = match self.props.get(&type_name) {
+            Some(deserializer) => deserializer,
+            None => return Err(ComponentRegistryError::UnknownProps(type_name)),
+        };
+        (deserializer)(data).map_err(|message| ComponentRegistryError::CouldNotDeserializeProps {
+            type_name,
+            message,
+        })
+    }
+
+    pub fn build_component(
+        &self,
+        definition: WidgetComponentDefinition,
+    ) -> Result<WidgetComponent, ComponentRegistryError> {
+        let WidgetComponentDefinition {
+            type_name,
+            key,
+            props,
+            shared_props,
+            listed_slots,
+            named_slots,
+        } = definition;
+        let processor = match self.components.get(&type_name) {
+            Some(processor) => *processor,
+            None => return Err(ComponentRegistryError::UnknownComponent(type_name)),
+        };
+        let props = match props {
+            Some(props) => self.build_props(props)?,
+            None => Props::default(),
+        };
+        let shared_props = match shared_props {
+            Some(props) => Some(self.build_props(props)?),
+            None => None,
+        };
+        let listed_slots = listed_slots
+            .into_iter()
+            .map(|slot| self.build_node(slot))
+            .collect::<Result<Vec<_>, _>>()?;
+        let named_slots = named_slots
+            .into_iter()
+            .map(|(name, slot)| Ok((name, self.build_node(slot)?)))
+            .collect::<Result<BTreeMap<_, _>, _>>()?;
+        Ok(WidgetComponent {
+            processor,
+            type_name,
+            key,
+            props,
+            shared_props,
+            listed_slots,
+            named_slots,
+        })
+    }
+
+    pub fn build_node(
+        &self,
+        definition: WidgetNodeDefinition,
+    ) -> Result<WidgetNode, ComponentRegistryError> {
+        match definition {
+            WidgetNodeDefinition::None => Ok(WidgetNode::None),
+            WidgetNodeDefinition::Component(component) => {
+                Ok(WidgetNode::Component(self.build_component(component)?))
+            }
+            WidgetNodeDefinition::Unit(unit) => Ok(WidgetNode::Unit(unit)),
+        }
+    }
+
+    pub fn deserialize_node<'de, D>(
+        &self,
+        deserializer: D,
+    ) -> Result<WidgetNode, ComponentRegistryError>
+    where
+        D: Deserializer<'de>,
+    {
+        let definition = WidgetNodeDefinition::deserialize(deserializer)
+            .map_err(|error| ComponentRegistryError::CouldNotDeserializeNode(error.to_string()))?;
+        self.build_node(definition)
+    }
+}
@@ -0,0 +1,194 @@
+use crate::{state::StateData, widget::WidgetId, MaybeSend};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_value::Value;
+use std::{
+    any::{type_name, Any, TypeId},
+    collections::HashMap,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum StateValue {
+    Bool(bool),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    F32(f32),
+    F64(f64),
+    Char(char),
+    String(String),
+    Unit,
+    Option(Option<Box<StateValue>>),
+    Newtype(Box<StateValue>),
+    Seq(Vec<StateValue>),
+    Map(Vec<(StateValue, StateValue)>),
+    Bytes(Vec<u8>),
+}
+
+impl From<Value> for StateValue {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Bool(v) => Self::Bool(v),
+            Value::U8(v) => Self::U8(v),
+            Value::U16(v) => Self::U16(v),
+            Value::U32(v) => Self::U32(v),
+            Value::U64(v) => Self::U64(v),
+            Value::I8(v) => Self::I8(v),
+            Value::I16(v) => Self::I16(v),
+            Value::I32(v) => Self::I32(v),
+            Value::I64(v) => Self::I64(v),
+            Value::F32(v) => Self::F32(v),
+            Value::F64(v) => Self::F64(v),
+            Value::Char(v) => Self::Char(v),
+            Value::String(v) => Self::String(v),
+            Value::Unit => Self::Unit,
+            Value::Option(v) => Self::Option(v.map(|v| Box::new((*v).into()))),
+            Value::Newtype(v) => Self::Newtype(Box::new((*v).into())),
+            Value::Seq(v) => Self::Seq(v.into_iter().map(|v| v.into()).collect()),
+            Value::Map(v) => Self::Map(v.into_iter().map(|(k, v)| (k.into(), v.into())).collect()),
+            Value::Bytes(v) => Self::Bytes(v),
+        }
+    }
+}
+
+impl From<StateValue> for Value {
+    fn from(value: StateValue) -> Self {
+        match value {
+            StateValue::Bool(v) => Self::Bool(v),
+            StateValue::U8(v) => Self::U8(v),
+            StateValue::U16(v) => Self::U16(v),
+            StateValue::U32(v) => Self::U32(v),
+            StateValue::U64(v) => Self::U64(v),
+            StateValue::I8(v) => Self::I8(v),
+            StateValue::I16(v) => Self::I16(v),
+            StateValue::I32(v) => Self::I32(v),
+            StateValue::I64(v) => Self::I64(v),
+            StateValue::F32(v) => Self::F32(v),
+            StateValue::F64(v) => Self::F64(v),
+            StateValue::Char(v) => Self::Char(v),
+            StateValue::String(v) => Self::String(v),
+            StateValue::Unit => Self::Unit,
+            StateValue::Option(v) => Self::Option(v.map(|v| Box::new((*v).into()))),
+            StateValue::Newtype(v) => Self::Newtype(Box::new((*v).into())),
+            StateValue::Seq(v) => Self::Seq(v.into_iter().map(|v| v.into()).collect()),
+            StateValue::Map(v) => {
+                Self::Map(v.into_iter().map(|(k, v)| (k.into(), v.into())).collect())
+            }
+            StateValue::Bytes(v) => Self::Bytes(v),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    pub id: WidgetId,
+    pub type_name: String,
+    pub value: StateValue,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatesSnapshot {
+    #[serde(default)]
+    pub states: Vec<StateSnapshot>,
+}
+
+struct StateSerializer {
+    type_name: String,
+    serialize: fn(&dyn Any) -> Option<StateValue>,
+    deserialize: fn(StateValue) -> Option<StateData>,
+}
+
+fn serialize_state<T>(data: &dyn Any) -> Option<StateValue>
+where
+    T: 'static + Serialize,
+{
+    let data = data.downcast_ref::<T>()?;
+    serde_value::to_value(data).ok().map(|value| value.into())
+}
+
+fn deserialize_state<T>(value: StateValue) -> Option<StateData>
+where
+    T: 'static + DeserializeOwned + MaybeSend,
+{
+    let value = Value::from(value);
+    T::deserialize(value)
+        .ok()
+        .map(|data| Box::new(data) as StateData)
+}
+
+#[derive(Default)]
+pub struct StatesRegistry {
+    serializers: HashMap<TypeId, StateSerializer>,
+    type_ids: HashMap<String, TypeId>,
+}
+
+impl std::fmt::Debug for StatesRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StatesRegistry")
+            .field("types", &self.type_ids.keys())
+            .finish()
+    }
+}
+
+impl StatesRegistry {
+    pub fn register<T>(&mut self)
+    where
+        T: 'static + Serialize + DeserializeOwned + MaybeSend,
+    {
+        self.register_named::<T>(type_name::<T>());
+    }
+
+    pub fn register_named<T>(&mut self, name: &str)
+    where
+        T: 'static + Serialize + DeserializeOwned + MaybeSend,
+    {
+        let type_id = TypeId::of::<T>();
+        self.unregister::<T>();
+        self.type_ids.insert(name.to_owned(), type_id);
+        self.serializers.insert(
+            type_id,
+            StateSerializer {
+                type_name: name.to_owned(),
+                serialize: serialize_state::<T>,
+                deserialize: deserialize_state::<T>,
+            },
+        );
+    }
+
+    pub fn unregister<T>(&mut self)
+    where
+        T: 'static,
+    {
+        if let Some(serializer) = self.serializers.remove(&TypeId::of::<T>()) {
+            self.type_ids.remove(&serializer.type_name);
+        }
+    }
+
+    pub fn is_registered<T>(&self) -> bool
+    where
+        T: 'static,
+    {
+        self.serializers.contains_key(&TypeId::of::<T>())
+    }
+
+    pub fn serialize(&self, id: &WidgetId, data: &StateData) -> Option<StateSnapshot> {
+        let serializer = self.serializers.get(&data.as_ref().type_id())?;
+        let value = (serializer.serialize)(data.as_ref())?;
+        Some(StateSnapshot {
+            id: id.to_owned(),
+            type_name: serializer.type_name.to_owned(),
+            value,
+        })
+    }
+
+    pub fn deserialize(&self, snapshot: StateSnapshot) -> Option<(WidgetId, StateData)> {
+        let type_id = self.type_ids.get(&snapshot.type_name)?;
+        let serializer = self.serializers.get(type_id)?;
+        let data = (serializer.deserialize)(snapshot.value)?;
+        Some((snapshot.id, data))
+    }
+}
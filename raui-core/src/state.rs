@@ -1,3 +1,4 @@
+use crate::MaybeSend;
 use std::{any::Any, sync::mpsc::Sender};
 
 pub enum StateError {
@@ -5,7 +6,10 @@ pub enum StateError {
     CouldNotWriteData,
 }
 
+#[cfg(not(feature = "sync"))]
 pub type StateData = Box<dyn Any>;
+#[cfg(feature = "sync")]
+pub type StateData = Box<dyn Any + Send>;
 
 #[derive(Clone)]
 pub struct StateUpdate(Sender<StateData>);
@@ -17,7 +21,7 @@ impl StateUpdate {
 
     pub fn write<T>(&self, data: T) -> Result<(), StateError>
     where
-        T: 'static,
+        T: 'static + MaybeSend,
     {
         if self.0.send(Box::new(data)).is_err() {
             Err(StateError::CouldNotWriteData)
@@ -47,7 +51,7 @@ impl<'a> State<'a> {
 
     pub fn write<T>(&self, data: T) -> Result<(), StateError>
     where
-        T: 'static,
+        T: 'static + MaybeSend,
     {
         self.update().write(data)
     }
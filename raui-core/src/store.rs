@@ -0,0 +1,152 @@
+use std::{
+    any::{Any, TypeId},
+    collections::{HashMap, HashSet},
+};
+
+pub enum StoreError {
+    CouldNotReadData,
+}
+
+#[derive(Default)]
+pub struct Store {
+    data: HashMap<TypeId, Box<dyn Any>>,
+    changed: HashSet<TypeId>,
+}
+
+impl std::fmt::Debug for Store {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Store {...}")
+    }
+}
+
+impl Store {
+    pub fn has<T>(&self) -> bool
+    where
+        T: 'static,
+    {
+        self.data.contains_key(&TypeId::of::<T>())
+    }
+
+    pub fn read<T>(&self) -> Result<&T, StoreError>
+    where
+        T: 'static,
+    {
+        match self.data.get(&TypeId::of::<T>()) {
+            Some(data) => data.downcast_ref::<T>().ok_or(StoreError::CouldNotReadData),
+            None => Err(StoreError::CouldNotReadData),
+        }
+    }
+
+    pub fn read_cloned<T>(&self) -> Result<T, StoreError>
+    where
+        T: 'static + Clone,
+    {
+        self.read::<T>().cloned()
+    }
+
+    pub fn read_cloned_or_default<T>(&self) -> T
+    where
+        T: 'static + Clone + Default,
+    {
+        self.read_cloned().unwrap_or_default()
+    }
+
+    pub fn write<T>(&mut self, data: T)
+    where
+        T: 'static,
+    {
+        let type_id = TypeId::of::<T>();
+        self.data.insert(type_id, Box::new(data));
+        self.changed.insert(type_id);
+    }
+
+    pub fn mutate<T, F>(&mut self, f: F) -> Result<(), StoreError>
+    where
+        T: 'static,
+        F: FnOnce(&mut T),
+    {
+        let type_id = TypeId::of::<T>();
+        match self
+            .data
+            .get_mut(&type_id)
+            .and_then(|data| data.downcast_mut::<T>())
+        {
+            Some(data) => {
+                f(data);
+                self.changed.insert(type_id);
+                Ok(())
+            }
+            None => Err(StoreError::CouldNotReadData),
+        }
+    }
+
+    pub fn remove<T>(&mut self) -> Option<T>
+    where
+        T: 'static,
+    {
+        let type_id = TypeId::of::<T>();
+        let data = self.data.remove(&type_id)?;
+        self.changed.insert(type_id);
+        data.downcast::<T>().ok().map(|data| *data)
+    }
+
+    #[inline]
+    pub fn is_changed(&self) -> bool {
+        !self.changed.is_empty()
+    }
+
+    pub(crate) fn take_changed(&mut self) -> HashSet<TypeId> {
+        std::mem::take(&mut self.changed)
+    }
+}
+
+pub struct StoreView<'a> {
+    store: &'a Store,
+    subscriptions: &'a mut HashSet<TypeId>,
+}
+
+impl<'a> StoreView<'a> {
+    pub fn new(store: &'a Store, subscriptions: &'a mut HashSet<TypeId>) -> Self {
+        Self {
+            store,
+            subscriptions,
+        }
+    }
+
+    pub fn subscribe<T>(&mut self)
+    where
+        T: 'static,
+    {
+        self.subscriptions.insert(TypeId::of::<T>());
+    }
+
+    pub fn has<T>(&mut self) -> bool
+    where
+        T: 'static,
+    {
+        self.subscribe::<T>();
+        self.store.has::<T>()
+    }
+
+    pub fn read<T>(&mut self) -> Result<&'a T, StoreError>
+    where
+        T: 'static,
+    {
+        self.subscribe::<T>();
+        self.store.read::<T>()
+    }
+
+    pub fn read_cloned<T>(&mut self) -> Result<T, StoreError>
+    where
+        T: 'static + Clone,
+    {
+        self.read::<T>().cloned()
+    }
+
+    pub fn read_cloned_or_default<T>(&mut self) -> T
+    where
+        T: 'static + Clone + Default,
+    {
+        self.read_cloned().unwrap_or_default()
+    }
+}
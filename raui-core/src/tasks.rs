@@ -0,0 +1,137 @@
+use crate::{messenger::Message, widget::WidgetId, MaybeSend};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::Sender,
+        Arc,
+    },
+    task::{Context, Poll, Wake, Waker},
+};
+
+pub type TaskFuture = Pin<Box<dyn Future<Output = Message>>>;
+pub type Task = (WidgetId, TaskFuture);
+
+#[derive(Clone)]
+pub struct TaskSpawner {
+    id: WidgetId,
+    sender: Sender<Task>,
+}
+
+impl std::fmt::Debug for TaskSpawner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskSpawner").field("id", &self.id).finish()
+    }
+}
+
+impl TaskSpawner {
+    pub fn new(id: WidgetId, sender: Sender<Task>) -> Self {
+        Self { id, sender }
+    }
+
+    #[inline]
+    pub fn id(&self) -> &WidgetId {
+        &self.id
+    }
+
+    pub fn spawn<F, T>(&self, future: F) -> bool
+    where
+        F: 'static + Future<Output = T>,
+        T: 'static + MaybeSend,
+    {
+        let future = async move { Box::new(future.await) as Message };
+        self.sender
+            .send((self.id.clone(), Box::pin(future)))
+            .is_ok()
+    }
+}
+
+struct TaskWaker {
+    ready: AtomicBool,
+}
+
+impl TaskWaker {
+    fn ready() -> Arc<Self> {
+        Arc::new(Self {
+            ready: AtomicBool::new(true),
+        })
+    }
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.ready.store(true, Ordering::Release);
+    }
+}
+
+#[derive(Default)]
+pub struct TasksExecutor {
+    tasks: Vec<(Task, Arc<TaskWaker>)>,
+}
+
+impl std::fmt::Debug for TasksExecutor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TasksExecutor")
+            .field("tasks", &self.tasks.len())
+            .finish()
+    }
+}
+
+impl TasksExecutor {
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    pub fn push(&mut self, task: Task) {
+        self.tasks.push((task, TaskWaker::ready()));
+    }
+
+    pub fn extend<I>(&mut self, tasks: I)
+    where
+        I: IntoIterator<Item = Task>,
+    {
+        self.tasks
+            .extend(tasks.into_iter().map(|task| (task, TaskWaker::ready())));
+    }
+
+    pub fn cancel(&mut self, id: &WidgetId) {
+        self.tasks.retain(|((task_id, _), _)| task_id != id);
+    }
+
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&WidgetId) -> bool,
+    {
+        self.tasks.retain(|((id, _), _)| f(id));
+    }
+
+    pub fn poll(&mut self) -> Vec<(WidgetId, Message)> {
+        let mut result = vec![];
+        self.tasks.retain_mut(|((id, future), task_waker)| {
+            if !task_waker.ready.swap(false, Ordering::AcqRel) {
+                return true;
+            }
+            let waker = Waker::from(task_waker.clone());
+            let mut context = Context::from_waker(&waker);
+            match future.as_mut().poll(&mut context) {
+                Poll::Ready(message) => {
+                    result.push((id.clone(), message));
+                    false
+                }
+                Poll::Pending => true,
+            }
+        });
+        result
+    }
+}
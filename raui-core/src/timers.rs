@@ -0,0 +1,171 @@
+use crate::{messenger::MessageSender, widget::WidgetId, Scalar};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::Sender,
+    },
+};
+
+static NEXT_TIMER_ID: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TimerId(u64);
+
+impl TimerId {
+    fn generate() -> Self {
+        Self(NEXT_TIMER_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TimerMessage {
+    Tick { delta_time: Scalar },
+    Delay { timer: TimerId },
+    Interval { timer: TimerId, count: usize },
+}
+
+#[derive(Debug, Clone)]
+pub enum TimerRequest {
+    Tick(WidgetId),
+    Start {
+        id: WidgetId,
+        timer: TimerId,
+        duration: Scalar,
+        repeat: bool,
+    },
+    Cancel(TimerId),
+}
+
+#[derive(Debug, Clone)]
+pub struct WidgetTimers {
+    id: WidgetId,
+    sender: Sender<TimerRequest>,
+}
+
+impl WidgetTimers {
+    pub fn new(id: WidgetId, sender: Sender<TimerRequest>) -> Self {
+        Self { id, sender }
+    }
+
+    pub fn tick(&self) -> bool {
+        self.sender
+            .send(TimerRequest::Tick(self.id.clone()))
+            .is_ok()
+    }
+
+    pub fn delay(&self, duration: Scalar) -> TimerId {
+        self.start(duration, false)
+    }
+
+    pub fn interval(&self, duration: Scalar) -> TimerId {
+        self.start(duration, true)
+    }
+
+    pub fn cancel(&self, timer: TimerId) -> bool {
+        self.sender.send(TimerRequest::Cancel(timer)).is_ok()
+    }
+
+    fn start(&self, duration: Scalar, repeat: bool) -> TimerId {
+        let timer = TimerId::generate();
+        let _ = self.sender.send(TimerRequest::Start {
+            id: self.id.clone(),
+            timer,
+            duration,
+            repeat,
+        });
+        timer
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Timer {
+    id: WidgetId,
+    timer: TimerId,
+    duration: Scalar,
+    remaining: Scalar,
+    repeat: bool,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Timers {
+    time: Scalar,
+    ticks: HashSet<WidgetId>,
+    timers: Vec<Timer>,
+}
+
+impl Timers {
+    #[inline]
+    pub fn time(&self) -> Scalar {
+        self.time
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ticks.is_empty() && self.timers.is_empty()
+    }
+
+    pub fn request(&mut self, request: TimerRequest) {
+        match request {
+            TimerRequest::Tick(id) => {
+                self.ticks.insert(id);
+            }
+            TimerRequest::Start {
+                id,
+                timer,
+                duration,
+                repeat,
+            } => self.timers.push(Timer {
+                id,
+                timer,
+                duration,
+                remaining: duration,
+                repeat,
+            }),
+            TimerRequest::Cancel(timer) => self.timers.retain(|item| item.timer != timer),
+        }
+    }
+
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&WidgetId) -> bool,
+    {
+        self.ticks.retain(|id| f(id));
+        self.timers.retain(|timer| f(&timer.id));
+    }
+
+    pub fn update(&mut self, delta_time: Scalar, message_sender: &MessageSender) -> bool {
+        let mut result = false;
+        self.time += delta_time;
+        for id in std::mem::take(&mut self.ticks) {
+            result |= message_sender.write(id, Box::new(TimerMessage::Tick { delta_time }));
+        }
+        self.timers.retain_mut(|timer| {
+            timer.remaining -= delta_time;
+            if timer.remaining > 0.0 {
+                return true;
+            }
+            if !timer.repeat {
+                let message = TimerMessage::Delay { timer: timer.timer };
+                result |= message_sender.write(timer.id.clone(), Box::new(message));
+                return false;
+            }
+            let count = if timer.duration > 0.0 {
+                let count = (-timer.remaining / timer.duration) as usize + 1;
+                timer.remaining += timer.duration * count as Scalar;
+                count
+            } else {
+                timer.remaining = 0.0;
+                1
+            };
+            let message = TimerMessage::Interval {
+                timer: timer.timer,
+                count,
+            };
+            result |= message_sender.write(timer.id.clone(), Box::new(message));
+            true
+        });
+        result
+    }
+}
@@ -0,0 +1,22 @@
+use crate::widget::{context::WidgetContext, node::WidgetNode, WidgetId};
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ErrorBoundaryProps {
+    pub fallback: WidgetNode,
+}
+implement_props_data!(ErrorBoundaryProps, PartialEq);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorBoundarySignal {
+    pub id: WidgetId,
+    pub source: Option<WidgetId>,
+    pub message: String,
+}
+
+pub fn error_boundary(mut context: WidgetContext) -> WidgetNode {
+    context
+        .take_listed_slots()
+        .into_iter()
+        .next()
+        .unwrap_or_default()
+}
@@ -1,8 +1,10 @@
+pub mod error_boundary;
+
 use crate::{
     props::Props,
     widget::{node::WidgetNode, FnWidget},
 };
-use std::{collections::HashMap, convert::TryFrom};
+use std::{collections::BTreeMap, convert::TryFrom};
 
 #[derive(Clone)]
 pub struct WidgetComponent {
@@ -10,8 +12,9 @@ pub struct WidgetComponent {
     pub type_name: String,
     pub key: Option<String>,
     pub props: Props,
+    pub shared_props: Option<Props>,
     pub listed_slots: Vec<WidgetNode>,
-    pub named_slots: HashMap<String, WidgetNode>,
+    pub named_slots: BTreeMap<String, WidgetNode>,
 }
 
 impl std::fmt::Debug for WidgetComponent {
@@ -31,6 +34,21 @@ impl std::fmt::Debug for WidgetComponent {
     }
 }
 
+impl PartialEq for WidgetComponent {
+    fn eq(&self, other: &Self) -> bool {
+        self.type_name == other.type_name
+            && self.key == other.key
+            && self.props.props_eq(&other.props)
+            && match (&self.shared_props, &other.shared_props) {
+                (Some(a), Some(b)) => a.props_eq(b),
+                (None, None) => true,
+                _ => false,
+            }
+            && self.listed_slots == other.listed_slots
+            && self.named_slots == other.named_slots
+    }
+}
+
 impl TryFrom<WidgetNode> for WidgetComponent {
     type Error = ();
 
@@ -1,23 +1,36 @@
 use crate::{
-    props::Props,
+    animator::WidgetAnimator,
+    hooks::Hooks,
+    interactions::focus::WidgetFocus,
+    props::{Props, SharedProps},
     state::State,
+    store::StoreView,
+    tasks::TaskSpawner,
+    timers::WidgetTimers,
     widget::{node::WidgetNode, WidgetId, WidgetLifeCycle},
 };
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 pub struct WidgetContext<'a> {
     pub id: &'a WidgetId,
     pub key: &'a str,
     pub props: &'a Props,
+    pub shared_props: &'a SharedProps,
     pub state: State<'a>,
+    pub store: StoreView<'a>,
     pub life_cycle: &'a mut WidgetLifeCycle,
-    pub named_slots: HashMap<String, WidgetNode>,
+    pub hooks: &'a mut Hooks,
+    pub tasks: TaskSpawner,
+    pub timers: WidgetTimers,
+    pub animator: WidgetAnimator<'a>,
+    pub focus: &'a mut WidgetFocus,
+    pub named_slots: BTreeMap<String, WidgetNode>,
     pub listed_slots: Vec<WidgetNode>,
 }
 
 impl<'a> WidgetContext<'a> {
-    pub fn take_named_slots(&mut self) -> HashMap<String, WidgetNode> {
-        std::mem::replace(&mut self.named_slots, HashMap::new())
+    pub fn take_named_slots(&mut self) -> BTreeMap<String, WidgetNode> {
+        std::mem::take(&mut self.named_slots)
     }
 
     pub fn take_listed_slots(&mut self) -> Vec<WidgetNode> {
@@ -192,6 +192,9 @@ macro_rules! widget {
             $(
                 : {$props:expr}
             )?
+            $(
+                | {$shared_props:expr}
+            )?
             $(
                 {
                     $($named_slot_name:ident = $named_slot_widget:tt)+
@@ -219,8 +222,14 @@ macro_rules! widget {
             $(
                 props = $crate::props::Props::from($props);
             )?
+            #[allow(unused_assignments)]
+            #[allow(unused_mut)]
+            let mut shared_props = None;
+            $(
+                shared_props = Some($crate::props::Props::from($shared_props));
+            )?
             #[allow(unused_mut)]
-            let mut named_slots = std::collections::HashMap::new();
+            let mut named_slots = std::collections::BTreeMap::new();
             $(
                 $(
                     let widget = widget_wrap!{$named_slot_widget};
@@ -245,6 +254,7 @@ macro_rules! widget {
                 type_name,
                 key,
                 props,
+                shared_props,
                 named_slots,
                 listed_slots,
             };
@@ -1,6 +1,6 @@
 use crate::widget::{component::WidgetComponent, unit::WidgetUnit};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum WidgetNode {
     None,
     Component(WidgetComponent),
@@ -8,7 +8,7 @@ use crate::{
 };
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ContentBoxItemLayout {
     #[serde(default)]
     pub anchors: Rect,
@@ -22,7 +22,7 @@ pub struct ContentBoxItemLayout {
     pub depth: Scalar,
 }
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ContentBoxItem {
     #[serde(default)]
     pub slot: WidgetUnit,
@@ -30,7 +30,7 @@ pub struct ContentBoxItem {
     pub layout: ContentBoxItemLayout,
 }
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ContentBox {
     #[serde(default)]
     pub id: WidgetId,
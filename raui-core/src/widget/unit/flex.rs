@@ -8,7 +8,7 @@ use crate::{
 };
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FlexBoxItem {
     #[serde(default)]
     pub slot: WidgetUnit,
@@ -24,6 +24,32 @@ pub struct FlexBoxItem {
     pub margin: Rect,
     #[serde(default)]
     pub align: Scalar,
+    #[serde(default)]
+    pub min_width: Scalar,
+    #[serde(default)]
+    pub max_width: Option<Scalar>,
+    #[serde(default)]
+    pub min_height: Scalar,
+    #[serde(default)]
+    pub max_height: Option<Scalar>,
+}
+
+impl FlexBoxItem {
+    pub fn clamp_width(&self, value: Scalar) -> Scalar {
+        let value = value.max(self.min_width);
+        match self.max_width {
+            Some(max) => value.min(max.max(self.min_width)),
+            None => value,
+        }
+    }
+
+    pub fn clamp_height(&self, value: Scalar) -> Scalar {
+        let value = value.max(self.min_height);
+        match self.max_height {
+            Some(max) => value.min(max.max(self.min_height)),
+            None => value,
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -58,7 +84,52 @@ impl FlexBoxDirection {
     }
 }
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FlexBoxDistribution {
+    Start,
+    Center,
+    End,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+}
+
+impl Default for FlexBoxDistribution {
+    fn default() -> Self {
+        Self::Start
+    }
+}
+
+impl FlexBoxDistribution {
+    pub fn offset_and_spacing(self, free_space: Scalar, count: usize) -> (Scalar, Scalar) {
+        let free_space = free_space.max(0.0);
+        if count == 0 {
+            return (0.0, 0.0);
+        }
+        match self {
+            Self::Start => (0.0, 0.0),
+            Self::Center => (free_space * 0.5, 0.0),
+            Self::End => (free_space, 0.0),
+            Self::SpaceBetween => {
+                if count > 1 {
+                    (0.0, free_space / (count - 1) as Scalar)
+                } else {
+                    (0.0, 0.0)
+                }
+            }
+            Self::SpaceAround => {
+                let spacing = free_space / count as Scalar;
+                (spacing * 0.5, spacing)
+            }
+            Self::SpaceEvenly => {
+                let spacing = free_space / (count + 1) as Scalar;
+                (spacing, spacing)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FlexBox {
     #[serde(default)]
     pub id: WidgetId,
@@ -70,6 +141,10 @@ pub struct FlexBox {
     pub separation: Scalar,
     #[serde(default)]
     pub wrap: bool,
+    #[serde(default)]
+    pub justify_content: FlexBoxDistribution,
+    #[serde(default)]
+    pub align_content: FlexBoxDistribution,
 }
 
 impl WidgetUnitData for FlexBox {
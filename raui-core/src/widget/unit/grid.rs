@@ -4,11 +4,12 @@ use crate::{
         utils::{IntRect, Rect},
         WidgetId,
     },
-    Scalar,
+    Integer, Scalar,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GridBoxItem {
     #[serde(default)]
     pub slot: WidgetUnit,
@@ -19,9 +20,68 @@ pub struct GridBoxItem {
     #[serde(default)]
     pub horizontal_align: Scalar,
     pub vertical_align: Scalar,
+    #[serde(default)]
+    pub col_span: usize,
+    #[serde(default)]
+    pub row_span: usize,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GridBoxAutoFlow {
+    None,
+    Row,
+    Column,
+}
+
+impl Default for GridBoxAutoFlow {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GridBoxTrackSize {
+    Exact(Scalar),
+    Fraction(Scalar),
+    Content,
 }
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+impl Default for GridBoxTrackSize {
+    fn default() -> Self {
+        Self::Fraction(1.0)
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GridBoxTrack {
+    #[serde(default)]
+    pub size: GridBoxTrackSize,
+    #[serde(default)]
+    pub min: Scalar,
+    #[serde(default)]
+    pub max: Option<Scalar>,
+}
+
+impl GridBoxTrack {
+    pub fn clamp(&self, value: Scalar) -> Scalar {
+        let value = value.max(self.min);
+        match self.max {
+            Some(max) => value.min(max.max(self.min)),
+            None => value,
+        }
+    }
+}
+
+impl From<GridBoxTrackSize> for GridBoxTrack {
+    fn from(size: GridBoxTrackSize) -> Self {
+        Self {
+            size,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GridBox {
     #[serde(default)]
     pub id: WidgetId,
@@ -31,6 +91,98 @@ pub struct GridBox {
     pub cols: usize,
     #[serde(default)]
     pub rows: usize,
+    #[serde(default)]
+    pub col_tracks: Vec<GridBoxTrack>,
+    #[serde(default)]
+    pub row_tracks: Vec<GridBoxTrack>,
+    #[serde(default)]
+    pub col_gap: Scalar,
+    #[serde(default)]
+    pub row_gap: Scalar,
+    #[serde(default)]
+    pub auto_flow: GridBoxAutoFlow,
+}
+
+impl GridBox {
+    pub fn cols_count(&self) -> usize {
+        self.cols.max(self.col_tracks.len())
+    }
+
+    pub fn rows_count(&self) -> usize {
+        self.rows.max(self.row_tracks.len())
+    }
+
+    pub fn space_occupancies(&self) -> Vec<IntRect> {
+        let (columns, lines) = match self.auto_flow {
+            GridBoxAutoFlow::None => {
+                return self.items.iter().map(|item| item.space_occupancy).collect();
+            }
+            GridBoxAutoFlow::Row => (false, self.cols_count().max(1) as Integer),
+            GridBoxAutoFlow::Column => (true, self.rows_count().max(1) as Integer),
+        };
+        let is_auto = |rect: &IntRect| rect.width() <= 0 || rect.height() <= 0;
+        let mut occupied = HashSet::new();
+        for item in &self.items {
+            let rect = item.space_occupancy;
+            if !is_auto(&rect) {
+                for col in rect.left..rect.right {
+                    for row in rect.top..rect.bottom {
+                        occupied.insert((col, row));
+                    }
+                }
+            }
+        }
+        let (mut cursor_main, mut cursor_cross) = (0, 0);
+        self.items
+            .iter()
+            .map(|item| {
+                if !is_auto(&item.space_occupancy) {
+                    return item.space_occupancy;
+                }
+                let col_span = item.col_span.max(1) as Integer;
+                let row_span = item.row_span.max(1) as Integer;
+                let (main_span, cross_span) = if columns {
+                    (row_span.min(lines), col_span)
+                } else {
+                    (col_span.min(lines), row_span)
+                };
+                loop {
+                    if cursor_main + main_span > lines {
+                        cursor_main = 0;
+                        cursor_cross += 1;
+                    }
+                    let rect = if columns {
+                        IntRect {
+                            left: cursor_cross,
+                            right: cursor_cross + cross_span,
+                            top: cursor_main,
+                            bottom: cursor_main + main_span,
+                        }
+                    } else {
+                        IntRect {
+                            left: cursor_main,
+                            right: cursor_main + main_span,
+                            top: cursor_cross,
+                            bottom: cursor_cross + cross_span,
+                        }
+                    };
+                    let free = (rect.left..rect.right).all(|col| {
+                        (rect.top..rect.bottom).all(|row| !occupied.contains(&(col, row)))
+                    });
+                    if free {
+                        for col in rect.left..rect.right {
+                            for row in rect.top..rect.bottom {
+                                occupied.insert((col, row));
+                            }
+                        }
+                        cursor_main += main_span;
+                        return rect;
+                    }
+                    cursor_main += 1;
+                }
+            })
+            .collect()
+    }
 }
 
 impl WidgetUnitData for GridBox {
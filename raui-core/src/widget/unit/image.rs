@@ -9,7 +9,7 @@ use crate::{
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ImageBoxImageScaling {
     Strech,
     Frame(Scalar),
@@ -21,7 +21,7 @@ impl Default for ImageBoxImageScaling {
     }
 }
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ImageBoxImage {
     #[serde(default)]
     pub id: String,
@@ -31,7 +31,7 @@ pub struct ImageBoxImage {
     pub scaling: ImageBoxImageScaling,
 }
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ImageBoxProcedural {
     #[serde(default)]
     pub id: String,
@@ -39,7 +39,7 @@ pub struct ImageBoxProcedural {
     pub parameters: HashMap<String, Scalar>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ImageBoxMaterial {
     Color(Color),
     Image(ImageBoxImage),
@@ -52,8 +52,9 @@ impl Default for ImageBoxMaterial {
     }
 }
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ImageBoxSizeValue {
+    Content,
     Fill,
     Exact(Scalar),
 }
@@ -64,7 +65,7 @@ impl Default for ImageBoxSizeValue {
     }
 }
 
-#[derive(Debug, Default, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ImageBoxAspectRatio {
     #[serde(default)]
     pub horizontal_alignment: Scalar,
@@ -72,7 +73,7 @@ pub struct ImageBoxAspectRatio {
     pub vertical_alignment: Scalar,
 }
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ImageBox {
     #[serde(default)]
     pub id: WidgetId,
@@ -16,7 +16,7 @@ use crate::widget::{
 use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WidgetUnitInspectionNode {
     pub id: WidgetId,
     pub children: Vec<WidgetUnitInspectionNode>,
@@ -30,7 +30,7 @@ pub trait WidgetUnitData {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum WidgetUnit {
     None,
     ContentBox(ContentBox),
@@ -8,7 +8,7 @@ use crate::{
 };
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub enum SizeBoxSizeValue {
     Content,
     Fill,
@@ -21,7 +21,7 @@ impl Default for SizeBoxSizeValue {
     }
 }
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SizeBox {
     #[serde(default)]
     pub id: WidgetId,
@@ -33,6 +33,42 @@ pub struct SizeBox {
     pub height: SizeBoxSizeValue,
     #[serde(default)]
     pub margin: Rect,
+    #[serde(default)]
+    pub min_width: Scalar,
+    #[serde(default)]
+    pub max_width: Option<Scalar>,
+    #[serde(default)]
+    pub min_height: Scalar,
+    #[serde(default)]
+    pub max_height: Option<Scalar>,
+}
+
+impl SizeBox {
+    pub fn clamp_width(&self, value: Scalar) -> Scalar {
+        let value = value.max(self.min_width);
+        match self.max_width {
+            Some(max) => value.min(max.max(self.min_width)),
+            None => value,
+        }
+    }
+
+    pub fn clamp_height(&self, value: Scalar) -> Scalar {
+        let value = value.max(self.min_height);
+        match self.max_height {
+            Some(max) => value.min(max.max(self.min_height)),
+            None => value,
+        }
+    }
+
+    pub fn clamp_outer_width(&self, value: Scalar) -> Scalar {
+        let margin = self.margin.left + self.margin.right;
+        self.clamp_width(value - margin) + margin
+    }
+
+    pub fn clamp_outer_height(&self, value: Scalar) -> Scalar {
+        let margin = self.margin.top + self.margin.bottom;
+        self.clamp_height(value - margin) + margin
+    }
 }
 
 impl WidgetUnitData for SizeBox {
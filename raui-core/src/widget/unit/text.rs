@@ -49,7 +49,7 @@ impl TextBoxDirection {
     }
 }
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TextBoxFont {
     #[serde(default)]
     pub name: String,
@@ -61,8 +61,9 @@ pub struct TextBoxFont {
     pub italic: bool,
 }
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TextBoxSizeValue {
+    Content,
     Fill,
     Exact(Scalar),
 }
@@ -73,7 +74,7 @@ impl Default for TextBoxSizeValue {
     }
 }
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TextBox {
     #[serde(default)]
     pub id: WidgetId,
@@ -5,19 +5,19 @@ use std::{
     marker::PhantomData,
 };
 
-#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Vec2 {
     pub x: Scalar,
     pub y: Scalar,
 }
 
-#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct IntVec2 {
     pub x: Integer,
     pub y: Integer,
 }
 
-#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Rect {
     pub left: Scalar,
     pub right: Scalar,
@@ -43,9 +43,14 @@ impl Rect {
             y: self.height(),
         }
     }
+
+    #[inline]
+    pub fn contains(&self, point: Vec2) -> bool {
+        point.x >= self.left && point.x < self.right && point.y >= self.top && point.y < self.bottom
+    }
 }
 
-#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct IntRect {
     pub left: Integer,
     pub right: Integer,
@@ -73,7 +78,7 @@ impl IntRect {
     }
 }
 
-#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Color {
     pub r: Scalar,
     pub g: Scalar,
@@ -3,7 +3,7 @@ use ggez::{
     Context,
 };
 use raui_core::{
-    layout::Layout,
+    layout::{image_size_provider::ImageSizeProvider, Layout},
     renderer::Renderer,
     widget::{
         unit::{
@@ -11,7 +11,7 @@ use raui_core::{
             text::TextBoxAlignment,
             WidgetUnit,
         },
-        utils::{lerp, Rect},
+        utils::{lerp, Rect, Vec2},
         WidgetId,
     },
     Scalar,
@@ -34,6 +34,15 @@ pub struct GgezResources {
     pub images: HashMap<String, Image>,
 }
 
+impl ImageSizeProvider for GgezResources {
+    fn image_size(&self, id: &str) -> Option<Vec2> {
+        self.images.get(id).map(|image| Vec2 {
+            x: image.width() as Scalar,
+            y: image.height() as Scalar,
+        })
+    }
+}
+
 pub struct GgezRenderer<'a> {
     context: &'a mut Context,
     resources: &'a mut GgezResources,
@@ -100,74 +109,37 @@ impl<'a> GgezRenderer<'a> {
                 ImageBoxMaterial::Image(image) => {
                     if let Some(item) = layout.items.get(&unit.id) {
                         if let Some(resource) = self.resources.images.get(&image.id) {
-                            let rect = if let Some(aspect) = unit.content_keep_aspect_ratio {
-                                let ox = item.ui_space.left;
-                                let oy = item.ui_space.top;
-                                let width = resource.width() as Scalar;
-                                let height = resource.height() as Scalar;
-                                if item.ui_space.width() >= item.ui_space.height() {
-                                    if width >= height {
-                                        let h = item.ui_space.height();
-                                        let w = h * width / height;
-                                        let o = lerp(
+                            // NOTE:
+                            // layout engine with image size provider already fits the rect,
+                            // in which case fitting it again here leaves it unchanged.
+                            let rect = match unit.content_keep_aspect_ratio {
+                                Some(aspect) if resource.width() > 0 && resource.height() > 0 => {
+                                    let width = resource.width() as Scalar;
+                                    let height = resource.height() as Scalar;
+                                    let scale = (item.ui_space.width() / width)
+                                        .min(item.ui_space.height() / height);
+                                    let w = width * scale;
+                                    let h = height * scale;
+                                    let ox = item.ui_space.left
+                                        + lerp(
                                             0.0,
                                             item.ui_space.width() - w,
                                             aspect.horizontal_alignment,
                                         );
-                                        Rect {
-                                            left: o + ox,
-                                            right: w + o + ox,
-                                            top: oy,
-                                            bottom: h + oy,
-                                        }
-                                    } else {
-                                        let w = item.ui_space.width();
-                                        let h = w * height / width;
-                                        let o = lerp(
-                                            0.0,
-                                            item.ui_space.height() - h,
-                                            aspect.vertical_alignment,
-                                        );
-                                        Rect {
-                                            left: ox,
-                                            right: w + ox,
-                                            top: o + oy,
-                                            bottom: h + o + oy,
-                                        }
-                                    }
-                                } else {
-                                    if width >= height {
-                                        let w = item.ui_space.width();
-                                        let h = w * height / width;
-                                        let o = lerp(
+                                    let oy = item.ui_space.top
+                                        + lerp(
                                             0.0,
                                             item.ui_space.height() - h,
                                             aspect.vertical_alignment,
                                         );
-                                        Rect {
-                                            left: ox,
-                                            right: w + ox,
-                                            top: o + oy,
-                                            bottom: h + o + oy,
-                                        }
-                                    } else {
-                                        let h = item.ui_space.height();
-                                        let w = h * width / height;
-                                        let o = lerp(
-                                            0.0,
-                                            item.ui_space.width() - w,
-                                            aspect.horizontal_alignment,
-                                        );
-                                        Rect {
-                                            left: o + ox,
-                                            right: w + o + ox,
-                                            top: oy,
-                                            bottom: h + oy,
-                                        }
+                                    Rect {
+                                        left: ox,
+                                        right: ox + w,
+                                        top: oy,
+                                        bottom: oy + h,
                                     }
                                 }
-                            } else {
-                                item.ui_space
+                                _ => item.ui_space,
                             };
                             let mut builder = MeshBuilder::new();
                             match image.scaling {
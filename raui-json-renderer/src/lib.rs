@@ -1,4 +1,9 @@
-use raui_core::{layout::Layout, renderer::Renderer, widget::unit::WidgetUnit};
+use raui_core::{
+    layout::Layout,
+    loader::{Loader, LoaderError},
+    renderer::Renderer,
+    widget::unit::WidgetUnit,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Default, Copy, Clone, Serialize, Deserialize)]
@@ -25,3 +30,33 @@ impl Renderer<serde_json::Value, serde_json::Error> for JsonRenderer {
         serde_json::to_value(tree)
     }
 }
+
+#[derive(Debug, Default, Copy, Clone)]
+pub struct JsonLoader;
+
+impl JsonLoader {
+    fn error(error: serde_json::Error) -> LoaderError {
+        let message = error.to_string();
+        if error.line() == 0 {
+            return LoaderError::new(message, None);
+        }
+        let suffix = format!(" at line {} column {}", error.line(), error.column());
+        LoaderError::at_line_column(
+            message.trim_end_matches(&suffix).to_owned(),
+            error.line(),
+            error.column(),
+        )
+    }
+}
+
+impl Loader<&str, LoaderError> for JsonLoader {
+    fn load(&mut self, source: &str) -> Result<WidgetUnit, LoaderError> {
+        serde_json::from_str(source).map_err(Self::error)
+    }
+}
+
+impl Loader<serde_json::Value, LoaderError> for JsonLoader {
+    fn load(&mut self, source: serde_json::Value) -> Result<WidgetUnit, LoaderError> {
+        serde_json::from_value(source).map_err(Self::error)
+    }
+}
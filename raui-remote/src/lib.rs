@@ -0,0 +1,461 @@
+use raui_binary_renderer::{BinaryLoader, BinaryRenderer};
+use raui_core::{
+    diff::{apply, diff, WidgetUnitPatch, WidgetUnitPatchError},
+    interactions::Interaction,
+    layout::{Layout, LayoutItem},
+    loader::{Loader, LoaderError},
+    renderer::Renderer,
+    widget::{unit::WidgetUnit, utils::Rect, WidgetId},
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    convert::TryFrom,
+    io::{ErrorKind, Read, Write},
+    sync::{Arc, Mutex},
+};
+
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 16 * 1024 * 1024;
+
+#[derive(Debug)]
+pub enum RemoteError {
+    Io(std::io::Error),
+    Serialization(bincode::Error),
+    FrameTooLarge(usize),
+    Patch(WidgetUnitPatchError),
+    Load(LoaderError),
+}
+
+impl From<std::io::Error> for RemoteError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<bincode::Error> for RemoteError {
+    fn from(error: bincode::Error) -> Self {
+        Self::Serialization(error)
+    }
+}
+
+impl From<WidgetUnitPatchError> for RemoteError {
+    fn from(error: WidgetUnitPatchError) -> Self {
+        Self::Patch(error)
+    }
+}
+
+impl From<LoaderError> for RemoteError {
+    fn from(error: LoaderError) -> Self {
+        Self::Load(error)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayoutPatch {
+    #[serde(default)]
+    pub ui_space: Option<Rect>,
+    #[serde(default)]
+    pub changed: Vec<(WidgetId, LayoutItem)>,
+    #[serde(default)]
+    pub removed: Vec<WidgetId>,
+}
+
+impl LayoutPatch {
+    pub fn new(from: &Layout, to: &Layout) -> Self {
+        let ui_space = if from.ui_space != to.ui_space {
+            Some(to.ui_space)
+        } else {
+            None
+        };
+        let changed = to
+            .items
+            .iter()
+            .filter(|(id, item)| from.items.get(*id) != Some(*item))
+            .map(|(id, item)| (id.to_owned(), *item))
+            .collect();
+        let removed = from
+            .items
+            .keys()
+            .filter(|id| !to.items.contains_key(*id))
+            .cloned()
+            .collect();
+        Self {
+            ui_space,
+            changed,
+            removed,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ui_space.is_none() && self.changed.is_empty() && self.removed.is_empty()
+    }
+
+    pub fn apply(&self, layout: &mut Layout) {
+        if let Some(ui_space) = self.ui_space {
+            layout.ui_space = ui_space;
+        }
+        for id in &self.removed {
+            layout.items.remove(id);
+        }
+        layout.items.extend(self.changed.iter().cloned());
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ServerMessage {
+    Snapshot {
+        tree: Vec<u8>,
+        layout: Layout,
+    },
+    Patch {
+        patches: Vec<WidgetUnitPatch>,
+        layout: LayoutPatch,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ClientMessage {
+    Interaction(Interaction),
+}
+
+pub fn write_frame<W, T>(writer: &mut W, data: &T, max_size: usize) -> Result<(), RemoteError>
+where
+    W: Write,
+    T: Serialize,
+{
+    let bytes = bincode::serialize(data)?;
+    if bytes.len() > max_size {
+        return Err(RemoteError::FrameTooLarge(bytes.len()));
+    }
+    let size = match u32::try_from(bytes.len()) {
+        Ok(size) => size,
+        Err(_) => return Err(RemoteError::FrameTooLarge(bytes.len())),
+    };
+    writer.write_all(&size.to_le_bytes())?;
+    writer.write_all(&bytes)?;
+    writer.flush()?;
+    Ok(())
+}
+
+pub fn read_frame<R, T>(reader: &mut R, max_size: usize) -> Result<T, RemoteError>
+where
+    R: Read,
+    T: DeserializeOwned,
+{
+    let mut size = [0; 4];
+    reader.read_exact(&mut size)?;
+    let size = u32::from_le_bytes(size) as usize;
+    if size > max_size {
+        return Err(RemoteError::FrameTooLarge(size));
+    }
+    let mut bytes = vec![0; size];
+    reader.read_exact(&mut bytes)?;
+    Ok(bincode::deserialize(&bytes)?)
+}
+
+#[derive(Debug, Default)]
+struct FrameBuffer {
+    bytes: Vec<u8>,
+}
+
+impl FrameBuffer {
+    fn read<R, T>(
+        &mut self,
+        reader: &mut R,
+        max_size: usize,
+        blocking: bool,
+    ) -> Result<Option<T>, RemoteError>
+    where
+        R: Read,
+        T: DeserializeOwned,
+    {
+        loop {
+            let required = if self.bytes.len() >= 4 {
+                let size = u32::from_le_bytes([
+                    self.bytes[0],
+                    self.bytes[1],
+                    self.bytes[2],
+                    self.bytes[3],
+                ]) as usize;
+                if size > max_size {
+                    self.bytes.clear();
+                    return Err(RemoteError::FrameTooLarge(size));
+                }
+                if self.bytes.len() >= size + 4 {
+                    let result = bincode::deserialize(&self.bytes[4..(size + 4)]);
+                    self.bytes.clear();
+                    return Ok(Some(result?));
+                }
+                size + 4
+            } else {
+                4
+            };
+            let start = self.bytes.len();
+            self.bytes.resize(required, 0);
+            let result = reader.read(&mut self.bytes[start..]);
+            let count = *result.as_ref().unwrap_or(&0);
+            self.bytes.truncate(start + count);
+            match result {
+                Ok(0) if blocking => return Err(RemoteError::Io(ErrorKind::UnexpectedEof.into())),
+                Ok(0) => return Ok(None),
+                Ok(_) => {}
+                Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                Err(error) if !blocking && error.kind() == ErrorKind::WouldBlock => {
+                    return Ok(None)
+                }
+                Err(error) => return Err(error.into()),
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RemoteServer<S> {
+    stream: S,
+    tree: Option<WidgetUnit>,
+    layout: Layout,
+    buffer: FrameBuffer,
+    max_frame_size: usize,
+}
+
+impl<S> RemoteServer<S>
+where
+    S: Read + Write,
+{
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            tree: None,
+            layout: Default::default(),
+            buffer: Default::default(),
+            max_frame_size: DEFAULT_MAX_FRAME_SIZE,
+        }
+    }
+
+    pub fn with_max_frame_size(mut self, size: usize) -> Self {
+        self.max_frame_size = size;
+        self
+    }
+
+    #[inline]
+    pub fn max_frame_size(&self) -> usize {
+        self.max_frame_size
+    }
+
+    #[inline]
+    pub fn stream(&self) -> &S {
+        &self.stream
+    }
+
+    #[inline]
+    pub fn stream_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    pub fn reset(&mut self) {
+        self.tree = None;
+    }
+
+    pub fn sync(&mut self, tree: &WidgetUnit, layout: &Layout) -> Result<bool, RemoteError> {
+        let message = match &self.tree {
+            Some(old) => {
+                let patches = diff(old, tree);
+                let layout = LayoutPatch::new(&self.layout, layout);
+                if patches.is_empty() && layout.is_empty() {
+                    return Ok(false);
+                }
+                ServerMessage::Patch { patches, layout }
+            }
+            None => ServerMessage::Snapshot {
+                tree: BinaryRenderer.render(tree, layout)?,
+                layout: layout.to_owned(),
+            },
+        };
+        write_frame(&mut self.stream, &message, self.max_frame_size)?;
+        self.tree = Some(tree.to_owned());
+        self.layout = layout.to_owned();
+        Ok(true)
+    }
+
+    pub fn receive(&mut self) -> Result<ClientMessage, RemoteError> {
+        match self
+            .buffer
+            .read(&mut self.stream, self.max_frame_size, true)?
+        {
+            Some(message) => Ok(message),
+            None => Err(RemoteError::Io(ErrorKind::UnexpectedEof.into())),
+        }
+    }
+
+    pub fn try_receive(&mut self) -> Result<Option<ClientMessage>, RemoteError> {
+        self.buffer
+            .read(&mut self.stream, self.max_frame_size, false)
+    }
+}
+
+#[derive(Debug)]
+pub struct RemoteClient<S> {
+    stream: S,
+    tree: WidgetUnit,
+    layout: Layout,
+    buffer: FrameBuffer,
+    max_frame_size: usize,
+}
+
+impl<S> RemoteClient<S>
+where
+    S: Read + Write,
+{
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            tree: Default::default(),
+            layout: Default::default(),
+            buffer: Default::default(),
+            max_frame_size: DEFAULT_MAX_FRAME_SIZE,
+        }
+    }
+
+    pub fn with_max_frame_size(mut self, size: usize) -> Self {
+        self.max_frame_size = size;
+        self
+    }
+
+    #[inline]
+    pub fn max_frame_size(&self) -> usize {
+        self.max_frame_size
+    }
+
+    #[inline]
+    pub fn stream(&self) -> &S {
+        &self.stream
+    }
+
+    #[inline]
+    pub fn stream_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    #[inline]
+    pub fn tree(&self) -> &WidgetUnit {
+        &self.tree
+    }
+
+    #[inline]
+    pub fn layout(&self) -> &Layout {
+        &self.layout
+    }
+
+    pub fn receive(&mut self) -> Result<(), RemoteError> {
+        if let Some(message) = self
+            .buffer
+            .read(&mut self.stream, self.max_frame_size, true)?
+        {
+            self.apply_message(message)?;
+        }
+        Ok(())
+    }
+
+    pub fn try_receive(&mut self) -> Result<bool, RemoteError> {
+        match self
+            .buffer
+            .read(&mut self.stream, self.max_frame_size, false)?
+        {
+            Some(message) => {
+                self.apply_message(message)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    fn apply_message(&mut self, message: ServerMessage) -> Result<(), RemoteError> {
+        match message {
+            ServerMessage::Snapshot { tree, layout } => {
+                self.tree = BinaryLoader.load(tree.as_slice())?;
+                self.layout = layout;
+            }
+            ServerMessage::Patch { patches, layout } => {
+                apply(&mut self.tree, &patches)?;
+                layout.apply(&mut self.layout);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn interact<T>(&mut self, interaction: T) -> Result<(), RemoteError>
+    where
+        T: Into<Interaction>,
+    {
+        let message = ClientMessage::Interaction(interaction.into());
+        write_frame(&mut self.stream, &message, self.max_frame_size)
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct MemoryStream {
+    input: Arc<Mutex<VecDeque<u8>>>,
+    output: Arc<Mutex<VecDeque<u8>>>,
+}
+
+impl MemoryStream {
+    pub fn pipe() -> (Self, Self) {
+        let a = Arc::new(Mutex::new(VecDeque::new()));
+        let b = Arc::new(Mutex::new(VecDeque::new()));
+        (
+            Self {
+                input: a.clone(),
+                output: b.clone(),
+            },
+            Self {
+                input: b,
+                output: a,
+            },
+        )
+    }
+
+    pub fn available(&self) -> usize {
+        self.input
+            .lock()
+            .map(|input| input.len())
+            .unwrap_or_default()
+    }
+}
+
+impl Read for MemoryStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut input = match self.input.lock() {
+            Ok(input) => input,
+            Err(_) => return Err(std::io::ErrorKind::BrokenPipe.into()),
+        };
+        let count = buf.len().min(input.len());
+        for (target, source) in buf.iter_mut().zip(input.drain(..count)) {
+            *target = source;
+        }
+        Ok(count)
+    }
+}
+
+impl Write for MemoryStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self.output.lock() {
+            Ok(mut output) => {
+                output.extend(buf);
+                Ok(buf.len())
+            }
+            Err(_) => Err(std::io::ErrorKind::BrokenPipe.into()),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
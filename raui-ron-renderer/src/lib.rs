@@ -1,4 +1,9 @@
-use raui_core::{layout::Layout, renderer::Renderer, widget::unit::WidgetUnit};
+use raui_core::{
+    layout::Layout,
+    loader::{Loader, LoaderError},
+    renderer::Renderer,
+    widget::unit::WidgetUnit,
+};
 use ron::ser::PrettyConfig;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
@@ -33,3 +38,29 @@ impl Renderer<ron::value::Value, ron::error::Error> for RonValueRenderer {
         }
     }
 }
+
+#[derive(Debug, Default, Copy, Clone)]
+pub struct RonLoader;
+
+impl RonLoader {
+    fn error(error: ron::error::Error) -> LoaderError {
+        let ron::error::Error { code, position } = error;
+        if position.line == 0 && position.col == 0 {
+            LoaderError::new(code.to_string(), None)
+        } else {
+            LoaderError::at_line_column(code.to_string(), position.line, position.col)
+        }
+    }
+}
+
+impl Loader<&str, LoaderError> for RonLoader {
+    fn load(&mut self, source: &str) -> Result<WidgetUnit, LoaderError> {
+        ron::de::from_str(source).map_err(Self::error)
+    }
+}
+
+impl Loader<ron::value::Value, LoaderError> for RonLoader {
+    fn load(&mut self, source: ron::value::Value) -> Result<WidgetUnit, LoaderError> {
+        source.into_rust().map_err(Self::error)
+    }
+}
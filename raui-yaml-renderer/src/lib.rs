@@ -1,4 +1,9 @@
-use raui_core::{layout::Layout, renderer::Renderer, widget::unit::WidgetUnit};
+use raui_core::{
+    layout::Layout,
+    loader::{Loader, LoaderError},
+    renderer::Renderer,
+    widget::unit::WidgetUnit,
+};
 
 #[derive(Debug, Default, Copy, Clone)]
 pub struct YamlRenderer;
@@ -21,3 +26,35 @@ impl Renderer<serde_yaml::Value, serde_yaml::Error> for YamlValueRenderer {
         serde_yaml::to_value(tree)
     }
 }
+
+#[derive(Debug, Default, Copy, Clone)]
+pub struct YamlLoader;
+
+impl YamlLoader {
+    fn error(error: serde_yaml::Error) -> LoaderError {
+        let message = error.to_string();
+        match error.location() {
+            Some(location) => {
+                let suffix = format!(" at line {} column {}", location.line(), location.column());
+                LoaderError::at_line_column(
+                    message.trim_end_matches(&suffix).to_owned(),
+                    location.line(),
+                    location.column(),
+                )
+            }
+            None => LoaderError::new(message, None),
+        }
+    }
+}
+
+impl Loader<&str, LoaderError> for YamlLoader {
+    fn load(&mut self, source: &str) -> Result<WidgetUnit, LoaderError> {
+        serde_yaml::from_str(source).map_err(Self::error)
+    }
+}
+
+impl Loader<serde_yaml::Value, LoaderError> for YamlLoader {
+    fn load(&mut self, source: serde_yaml::Value) -> Result<WidgetUnit, LoaderError> {
+        serde_yaml::from_value(source).map_err(Self::error)
+    }
+}
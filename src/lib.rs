@@ -9,6 +9,11 @@ pub mod core {
     pub use raui_core::*;
 }
 
+#[cfg(feature = "remote")]
+pub mod remote {
+    pub use raui_remote::*;
+}
+
 pub mod renderer {
     #[cfg(feature = "binary")]
     pub mod binary {
@@ -40,6 +45,8 @@ pub mod prelude {
     pub use raui_html_renderer::*;
     #[cfg(feature = "json")]
     pub use raui_json_renderer::*;
+    #[cfg(feature = "remote")]
+    pub use raui_remote::*;
     #[cfg(feature = "ron")]
     pub use raui_ron_renderer::*;
     #[cfg(feature = "yaml")]
@@ -3,6 +3,35 @@
 use crate::prelude::*;
 use std::str::FromStr;
 
+fn rendered_text(application: &Application) -> String {
+    match application.rendered_tree() {
+        WidgetUnit::TextBox(text) => text.text.to_owned(),
+        _ => panic!("expected text box"),
+    }
+}
+
+fn layout_rect(application: &Application, key: &str) -> Rect {
+    application
+        .layout_data()
+        .items
+        .iter()
+        .find(|(id, _)| id.key() == key)
+        .map(|(_, item)| item.ui_space)
+        .unwrap()
+}
+
+fn layout_rects(application: &Application, type_name: &str) -> Vec<Rect> {
+    let mut result = application
+        .layout_data()
+        .items
+        .iter()
+        .filter(|(id, _)| id.type_name() == type_name)
+        .map(|(id, item)| (id.key().to_owned(), item.ui_space))
+        .collect::<Vec<_>>();
+    result.sort_by(|a, b| a.0.cmp(&b.0));
+    result.into_iter().map(|(_, rect)| rect).collect()
+}
+
 #[test]
 fn test_macro() {
     fn app(_context: WidgetContext) -> WidgetNode {
@@ -427,3 +456,2545 @@ fn test_layout_wrapping() {
         println!("=== LAYOUT:\n{:#?}", application.layout_data());
     }
 }
+
+#[test]
+fn test_pointer_interactions() {
+    widget_hook! {
+        use_pointer_signals(life_cycle) {
+            life_cycle.change(|_, _, _, messenger, signals| {
+                for msg in messenger.messages {
+                    if let Some(msg) = msg.downcast_ref::<PointerMessage>() {
+                        signals.write(Box::new(msg.action));
+                    }
+                }
+            });
+        }
+    }
+
+    widget_component! {
+        panel(id, props) [use_pointer_signals] {
+            widget! {{{
+                ImageBox {
+                    id: id.to_owned(),
+                    width: ImageBoxSizeValue::Exact(props.read_cloned_or_default::<Scalar>()),
+                    height: ImageBoxSizeValue::Exact(props.read_cloned_or_default::<Scalar>()),
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    widget_component! {
+        stack(id, listed_slots) {
+            let items = listed_slots
+                .into_iter()
+                .enumerate()
+                .map(|(i, slot)| ContentBoxItem {
+                    slot: slot.try_into().unwrap(),
+                    layout: ContentBoxItemLayout {
+                        depth: -(i as Scalar),
+                        ..Default::default()
+                    },
+                })
+                .collect::<Vec<_>>();
+
+            widget! {{{
+                ContentBox {
+                    id: id.to_owned(),
+                    items,
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    use std::convert::TryInto;
+
+    let mut application = Application::new();
+    application.apply(widget! {
+        (#{"stack"} stack [
+            (#{"big"} panel: {100.0 as Scalar})
+            (#{"small"} panel: {50.0 as Scalar})
+        ])
+    });
+    let view = Rect {
+        left: 0.0,
+        right: 200.0,
+        top: 0.0,
+        bottom: 200.0,
+    };
+    application
+        .layout(view, &mut DefaultLayoutEngine::new())
+        .unwrap();
+
+    let hits = hit_test(
+        application.rendered_tree(),
+        application.layout_data(),
+        Vec2 { x: 25.0, y: 25.0 },
+    );
+    assert_eq!(
+        hits.iter().map(|id| id.key()).collect::<Vec<_>>(),
+        vec!["big", "stack"]
+    );
+
+    let mut interactions = DefaultInteractionsEngine::default();
+    interactions.pointer(PointerEvent::Down {
+        button: PointerButton::Primary,
+        position: Vec2 { x: 75.0, y: 75.0 },
+    });
+    interactions.pointer(PointerEvent::Up {
+        button: PointerButton::Primary,
+        position: Vec2 { x: 75.0, y: 75.0 },
+    });
+    interactions.scroll(ScrollEvent {
+        position: Vec2 { x: 75.0, y: 75.0 },
+        delta: Vec2 { x: 0.0, y: -1.0 },
+    });
+    assert!(application.interact(&mut interactions).unwrap());
+    application.process();
+    let actions = application
+        .signals()
+        .read_all()
+        .into_iter()
+        .filter(|(id, _)| id.key() == "big")
+        .filter_map(|(_, msg)| msg.downcast_ref::<PointerAction>().copied())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        actions,
+        vec![
+            PointerAction::Enter,
+            PointerAction::Down(PointerButton::Primary),
+            PointerAction::Up(PointerButton::Primary),
+            PointerAction::Click(PointerButton::Primary),
+            PointerAction::Scroll(Vec2 { x: 0.0, y: -1.0 }),
+        ]
+    );
+}
+
+#[test]
+fn test_focus_navigation() {
+    widget_component! {
+        button(id, focus) {
+            focus.focusable(0);
+
+            widget! {{{
+                SizeBox {
+                    id: id.to_owned(),
+                    width: SizeBoxSizeValue::Exact(50.0),
+                    height: SizeBoxSizeValue::Exact(50.0),
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    widget_component! {
+        grid(id, listed_slots) {
+            let items = listed_slots
+                .into_iter()
+                .enumerate()
+                .map(|(i, slot)| GridBoxItem {
+                    slot: slot.try_into().unwrap(),
+                    space_occupancy: IntRect {
+                        left: i as Integer % 2,
+                        right: i as Integer % 2 + 1,
+                        top: i as Integer / 2,
+                        bottom: i as Integer / 2 + 1,
+                    },
+                    ..Default::default()
+                })
+                .collect::<Vec<_>>();
+
+            widget! {{{
+                GridBox {
+                    id: id.to_owned(),
+                    items,
+                    cols: 2,
+                    rows: 2,
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    use std::convert::TryInto;
+
+    let mut application = Application::new();
+    application.apply(widget! {
+        (grid [
+            (#{"a"} button)
+            (#{"b"} button)
+            (#{"c"} button)
+        ])
+    });
+    let view = Rect {
+        left: 0.0,
+        right: 200.0,
+        top: 0.0,
+        bottom: 200.0,
+    };
+    application
+        .layout(view, &mut DefaultLayoutEngine::default())
+        .unwrap();
+    let focused = |application: &Application| application.focused().map(|id| id.key().to_owned());
+
+    let mut interactions = DefaultInteractionsEngine::default();
+    interactions.key(KeyEvent::Down {
+        key: KeyCode::Tab,
+        modifiers: Default::default(),
+    });
+    application.interact(&mut interactions).unwrap();
+    assert_eq!(focused(&application), Some("a".to_owned()));
+
+    interactions.navigate(FocusDirection::Down);
+    application.interact(&mut interactions).unwrap();
+    assert_eq!(focused(&application), Some("c".to_owned()));
+
+    interactions.navigate(FocusDirection::Right);
+    application.interact(&mut interactions).unwrap();
+    assert_eq!(focused(&application), Some("b".to_owned()));
+
+    interactions.key(KeyEvent::Down {
+        key: KeyCode::Up,
+        modifiers: Default::default(),
+    });
+    interactions.key(KeyEvent::Down {
+        key: KeyCode::Left,
+        modifiers: Default::default(),
+    });
+    application.interact(&mut interactions).unwrap();
+    assert_eq!(focused(&application), Some("a".to_owned()));
+
+    interactions.key(KeyEvent::Down {
+        key: KeyCode::Tab,
+        modifiers: KeyModifiers {
+            shift: true,
+            ..Default::default()
+        },
+    });
+    application.interact(&mut interactions).unwrap();
+    assert_eq!(focused(&application), Some("c".to_owned()));
+
+    interactions.unbind_navigation_key(KeyCode::Up, Default::default());
+    interactions.key(KeyEvent::Down {
+        key: KeyCode::Up,
+        modifiers: Default::default(),
+    });
+    application.interact(&mut interactions).unwrap();
+    assert_eq!(focused(&application), Some("c".to_owned()));
+    interactions.bind_navigation_key(KeyCode::Char('w'), Default::default(), FocusDirection::Up);
+    interactions.key(KeyEvent::Down {
+        key: KeyCode::Char('w'),
+        modifiers: Default::default(),
+    });
+    application.interact(&mut interactions).unwrap();
+    assert_eq!(focused(&application), Some("a".to_owned()));
+    interactions.navigate(FocusDirection::Down);
+    application.interact(&mut interactions).unwrap();
+    assert_eq!(focused(&application), Some("c".to_owned()));
+
+    interactions.pointer(PointerEvent::Down {
+        button: PointerButton::Primary,
+        position: Vec2 { x: 125.0, y: 25.0 },
+    });
+    application.interact(&mut interactions).unwrap();
+    assert_eq!(focused(&application), Some("b".to_owned()));
+
+    application.apply(widget! {
+        (grid [
+            (#{"a"} button)
+        ])
+    });
+    assert_eq!(focused(&application), None);
+}
+
+#[test]
+fn test_memoization() {
+    use std::{
+        convert::TryInto,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    static PROCESSED: AtomicUsize = AtomicUsize::new(0);
+
+    widget_component! {
+        label(id, props) {
+            PROCESSED.fetch_add(1, Ordering::SeqCst);
+
+            widget! {{{
+                TextBox {
+                    id: id.to_owned(),
+                    text: props.read_cloned_or_default::<String>(),
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    widget_hook! {
+        use_signal_on_mount(life_cycle) {
+            life_cycle.mount(|_, _, _, _, signals| {
+                signals.write(Box::new(()));
+            });
+        }
+    }
+
+    widget_component! {
+        noisy(id) [use_signal_on_mount] {
+            widget! {{{
+                TextBox {
+                    id: id.to_owned(),
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    widget_component! {
+        list(id, listed_slots) {
+            let items = listed_slots
+                .into_iter()
+                .map(|slot| FlexBoxItem {
+                    slot: slot.try_into().unwrap(),
+                    ..Default::default()
+                })
+                .collect::<Vec<_>>();
+
+            widget! {{{
+                FlexBox {
+                    id: id.to_owned(),
+                    items,
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    let mut application = Application::new();
+    application.set_memoization(true);
+    application.apply(widget! {
+        (#{"list"} list [
+            (#{"label"} label: {"Hello".to_owned()})
+            (#{"noisy"} noisy)
+        ])
+    });
+    assert_eq!(PROCESSED.load(Ordering::SeqCst), 1);
+    let (noisy_id, _) = application.signals().read().unwrap();
+
+    application.messenger().write(noisy_id, Box::new(()));
+    assert!(application.process());
+    assert_eq!(PROCESSED.load(Ordering::SeqCst), 1);
+
+    application.apply(widget! {
+        (#{"list"} list [
+            (#{"label"} label: {"Hello".to_owned()})
+            (#{"noisy"} noisy)
+        ])
+    });
+    assert_eq!(PROCESSED.load(Ordering::SeqCst), 1);
+
+    application.apply(widget! {
+        (#{"list"} list [
+            (#{"label"} label: {"World".to_owned()})
+            (#{"noisy"} noisy)
+        ])
+    });
+    assert_eq!(PROCESSED.load(Ordering::SeqCst), 2);
+
+    application.set_memoization(false);
+    application.forced_process();
+    assert_eq!(PROCESSED.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_incremental_processing() {
+    use std::{
+        convert::TryInto,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    static PROCESSED: AtomicUsize = AtomicUsize::new(0);
+
+    widget_component! {
+        label(id, props) {
+            PROCESSED.fetch_add(1, Ordering::SeqCst);
+
+            widget! {{{
+                TextBox {
+                    id: id.to_owned(),
+                    text: props.read_cloned_or_default::<String>(),
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    widget_hook! {
+        use_counter(life_cycle) {
+            life_cycle.mount(|_, _, _, _, signals| {
+                signals.write(Box::new(()));
+            });
+
+            life_cycle.change(|_, _, state, messenger, _| {
+                if !messenger.messages.is_empty() {
+                    let counter = state.read::<usize>().copied().unwrap_or_default();
+                    let _ = state.write(counter + 1);
+                }
+            });
+        }
+    }
+
+    widget_component! {
+        counter(id, state) [use_counter] {
+            let counter = state.read::<usize>().copied().unwrap_or_default();
+
+            widget! {{{
+                TextBox {
+                    id: id.to_owned(),
+                    text: counter.to_string(),
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    widget_component! {
+        list(id, listed_slots) {
+            let items = listed_slots
+                .into_iter()
+                .map(|slot| FlexBoxItem {
+                    slot: slot.try_into().unwrap(),
+                    ..Default::default()
+                })
+                .collect::<Vec<_>>();
+
+            widget! {{{
+                FlexBox {
+                    id: id.to_owned(),
+                    items,
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    let mut application = Application::new();
+    application.apply(widget! {
+        (#{"list"} list [
+            (#{"label"} label: {"Hello".to_owned()})
+            (#{"counter"} counter)
+        ])
+    });
+    assert_eq!(PROCESSED.load(Ordering::SeqCst), 1);
+    assert!(application.does_render_changed());
+    let (counter_id, _) = application.signals().read().unwrap();
+
+    application
+        .messenger()
+        .write(counter_id.to_owned(), Box::new(()));
+    assert!(application.process());
+    assert!(application.process());
+    assert_eq!(PROCESSED.load(Ordering::SeqCst), 1);
+    assert!(application.does_render_changed());
+    assert!(application.changed_units().contains(&counter_id));
+    assert!(!application
+        .changed_units()
+        .iter()
+        .any(|id| id.key() == "label"));
+    if let WidgetUnit::FlexBox(list) = application.rendered_tree() {
+        if let WidgetUnit::TextBox(text) = &list.items[1].slot {
+            assert_eq!(text.text, "1");
+        } else {
+            panic!("expected text box");
+        }
+    } else {
+        panic!("expected flex box");
+    }
+
+    assert!(!application.process());
+    assert!(!application.does_render_changed());
+    assert!(application.changed_units().is_empty());
+
+    application.forced_process();
+    assert_eq!(PROCESSED.load(Ordering::SeqCst), 2);
+    assert!(application.does_render_changed());
+    assert!(application
+        .changed_units()
+        .iter()
+        .any(|id| id.key() == "label"));
+
+    application.apply(widget! {
+        (#{"list"} list [
+            (#{"counter"} counter)
+        ])
+    });
+    assert!(application.changed_units().contains(&counter_id));
+    assert!(application
+        .changed_units()
+        .iter()
+        .any(|id| id.key() == "label"));
+}
+
+#[test]
+fn test_shared_props() {
+    use std::convert::TryInto;
+
+    #[derive(Debug, Default, Clone, PartialEq)]
+    struct Theme(String);
+    implement_props_data!(Theme, PartialEq);
+
+    widget_component! {
+        themed_label(id, shared_props) {
+            widget! {{{
+                TextBox {
+                    id: id.to_owned(),
+                    text: shared_props.read_cloned_or_default::<Theme>().0,
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    widget_component! {
+        panel(id) {
+            widget! {
+                (#{"label"} themed_label)
+            }
+        }
+    }
+
+    widget_component! {
+        list(id, listed_slots) {
+            let items = listed_slots
+                .into_iter()
+                .map(|slot| FlexBoxItem {
+                    slot: slot.try_into().unwrap(),
+                    ..Default::default()
+                })
+                .collect::<Vec<_>>();
+
+            widget! {{{
+                FlexBox {
+                    id: id.to_owned(),
+                    items,
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    let mut application = Application::new();
+    application.apply(widget! {
+        (#{"list"} list | {Theme("dark".to_owned())} [
+            (#{"a"} themed_label)
+            (#{"b"} panel)
+            (#{"c"} panel | {Theme("light".to_owned())})
+            (#{"d"} list | {42u32} [
+                (#{"e"} themed_label)
+            ])
+        ])
+    });
+    let texts = match application.rendered_tree() {
+        WidgetUnit::FlexBox(list) => list
+            .items
+            .iter()
+            .map(|item| match &item.slot {
+                WidgetUnit::TextBox(text) => text.text.to_owned(),
+                WidgetUnit::FlexBox(list) => match &list.items[0].slot {
+                    WidgetUnit::TextBox(text) => text.text.to_owned(),
+                    _ => panic!("expected text box"),
+                },
+                _ => panic!("expected text box"),
+            })
+            .collect::<Vec<_>>(),
+        _ => panic!("expected flex box"),
+    };
+    assert_eq!(texts, vec!["dark", "dark", "light", "dark"]);
+}
+
+#[test]
+fn test_store() {
+    use std::{
+        convert::TryInto,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    static PROCESSED: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Debug, Default, Copy, Clone)]
+    struct Score(usize);
+
+    #[derive(Debug, Copy, Clone)]
+    struct Health;
+
+    widget_component! {
+        label(id, props) {
+            PROCESSED.fetch_add(1, Ordering::SeqCst);
+
+            widget! {{{
+                TextBox {
+                    id: id.to_owned(),
+                    text: props.read_cloned_or_default::<String>(),
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    widget_component! {
+        score(id, store) {
+            widget! {{{
+                TextBox {
+                    id: id.to_owned(),
+                    text: store.read_cloned_or_default::<Score>().0.to_string(),
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    widget_component! {
+        list(id, listed_slots) {
+            let items = listed_slots
+                .into_iter()
+                .map(|slot| FlexBoxItem {
+                    slot: slot.try_into().unwrap(),
+                    ..Default::default()
+                })
+                .collect::<Vec<_>>();
+
+            widget! {{{
+                FlexBox {
+                    id: id.to_owned(),
+                    items,
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    let score_text = |application: &Application| match application.rendered_tree() {
+        WidgetUnit::FlexBox(list) => match &list.items[1].slot {
+            WidgetUnit::TextBox(text) => text.text.to_owned(),
+            _ => panic!("expected text box"),
+        },
+        _ => panic!("expected flex box"),
+    };
+
+    let mut application = Application::new();
+    application.store_mut().write(Score(1));
+    application.apply(widget! {
+        (#{"list"} list [
+            (#{"label"} label: {"Score".to_owned()})
+            (#{"score"} score)
+        ])
+    });
+    assert_eq!(score_text(&application), "1");
+    assert_eq!(PROCESSED.load(Ordering::SeqCst), 1);
+
+    application.store_mut().write(Health);
+    assert!(!application.process());
+
+    application
+        .store_mut()
+        .mutate::<Score, _>(|score| score.0 += 1)
+        .ok()
+        .unwrap();
+    assert!(application.process());
+    assert_eq!(score_text(&application), "2");
+    assert_eq!(PROCESSED.load(Ordering::SeqCst), 1);
+
+    application.store_mut().remove::<Score>();
+    assert!(application.process());
+    assert_eq!(score_text(&application), "0");
+}
+
+#[test]
+fn test_hooks() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static EFFECTS: AtomicUsize = AtomicUsize::new(0);
+    static CLEANUPS: AtomicUsize = AtomicUsize::new(0);
+    static MEMOS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Debug, Copy, Clone)]
+    enum Action {
+        Add(usize),
+        Reset,
+    }
+
+    fn reducer(state: &usize, action: Action) -> usize {
+        match action {
+            Action::Add(value) => state + value,
+            Action::Reset => 0,
+        }
+    }
+
+    widget_component! {
+        counter(id, hooks, life_cycle) {
+            let (clicks, set_clicks) = hooks.use_state(|| 0usize);
+            let (total, dispatch) = hooks.use_reducer(reducer, || 10usize);
+            let label = hooks.use_memo(clicks, |clicks| {
+                MEMOS.fetch_add(1, Ordering::SeqCst);
+                format!("{} / {}", clicks, total)
+            });
+            hooks.use_effect(clicks, |_| {
+                EFFECTS.fetch_add(1, Ordering::SeqCst);
+                Some(Box::new(|| {
+                    CLEANUPS.fetch_add(1, Ordering::SeqCst);
+                }))
+            });
+
+            life_cycle.mount(|_, _, _, _, signals| {
+                signals.write(Box::new(()));
+            });
+
+            life_cycle.change(move |_, _, _, messenger, _| {
+                for msg in messenger.messages {
+                    if let Some(action) = msg.downcast_ref::<Action>() {
+                        dispatch.dispatch(*action);
+                    } else {
+                        set_clicks.update(|clicks| clicks + 1);
+                    }
+                }
+            });
+
+            widget! {{{
+                TextBox {
+                    id: id.to_owned(),
+                    text: label,
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    let mut application = Application::new();
+    application.apply(widget! {
+        (#{"counter"} counter)
+    });
+    assert_eq!(rendered_text(&application), "0 / 10");
+    assert_eq!(EFFECTS.load(Ordering::SeqCst), 1);
+    assert_eq!(CLEANUPS.load(Ordering::SeqCst), 0);
+    let (id, _) = application.signals().read().unwrap();
+
+    application
+        .messenger()
+        .write(id.to_owned(), Box::new(Action::Add(5)));
+    assert!(application.process());
+    assert!(application.process());
+    assert_eq!(rendered_text(&application), "0 / 10");
+    assert_eq!(MEMOS.load(Ordering::SeqCst), 1);
+    assert_eq!(EFFECTS.load(Ordering::SeqCst), 1);
+
+    application.messenger().write(id.to_owned(), Box::new(()));
+    assert!(application.process());
+    assert!(application.process());
+    assert_eq!(rendered_text(&application), "1 / 15");
+    assert_eq!(MEMOS.load(Ordering::SeqCst), 2);
+    assert_eq!(EFFECTS.load(Ordering::SeqCst), 2);
+    assert_eq!(CLEANUPS.load(Ordering::SeqCst), 1);
+
+    application.messenger().write(id, Box::new(Action::Reset));
+    assert!(application.process());
+    assert!(application.process());
+    assert_eq!(rendered_text(&application), "1 / 15");
+    assert_eq!(EFFECTS.load(Ordering::SeqCst), 2);
+
+    application.apply(widget! {()});
+    assert_eq!(CLEANUPS.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_named_slots_ids() {
+    use std::convert::TryInto;
+
+    widget_hook! {
+        use_signal_on_mount(life_cycle) {
+            life_cycle.mount(|_, _, _, _, signals| {
+                signals.write(Box::new(()));
+            });
+        }
+    }
+
+    widget_component! {
+        item(id) [use_signal_on_mount] {
+            widget! {{{
+                TextBox {
+                    id: id.to_owned(),
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    widget_component! {
+        slots(id, named_slots) {
+            widget! {{{
+                FlexBox {
+                    id: id.to_owned(),
+                    items: named_slots
+                        .into_values()
+                        .map(|slot| FlexBoxItem {
+                            slot: slot.try_into().unwrap(),
+                            ..Default::default()
+                        })
+                        .collect::<Vec<_>>(),
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    let ids = || {
+        let mut application = Application::new();
+        application.apply(widget! {
+            (slots {
+                header = (item)
+                content = (item)
+                footer = (item)
+                sidebar = (item)
+                toolbar = (item)
+                overlay = (item)
+                tooltip = (item)
+                status = (item)
+            })
+        });
+        application
+            .signals()
+            .read_all()
+            .into_iter()
+            .map(|(id, _)| id.to_string())
+            .collect::<Vec<_>>()
+    };
+    let names = [
+        "content", "footer", "header", "overlay", "sidebar", "status", "toolbar", "tooltip",
+    ];
+    let first = ids();
+    assert_eq!(first.len(), names.len());
+    for (id, name) in first.iter().zip(names.iter()) {
+        assert!(
+            id.ends_with(&format!("/<#{}>", name)),
+            "{} for {}",
+            id,
+            name
+        );
+    }
+    for _ in 0..10 {
+        assert_eq!(ids(), first);
+    }
+}
+
+#[test]
+fn test_slot_keys() {
+    use std::{collections::HashSet, convert::TryInto};
+
+    widget_hook! {
+        use_signal_on_mount(life_cycle) {
+            life_cycle.mount(|_, _, _, _, signals| {
+                signals.write(Box::new(()));
+            });
+        }
+    }
+
+    widget_component! {
+        item(id) [use_signal_on_mount] {
+            widget! {{{
+                TextBox {
+                    id: id.to_owned(),
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    widget_component! {
+        slots(id, listed_slots, named_slots) {
+            widget! {{{
+                FlexBox {
+                    id: id.to_owned(),
+                    items: listed_slots
+                        .into_iter()
+                        .chain(named_slots.into_values())
+                        .map(|slot| FlexBoxItem {
+                            slot: slot.try_into().unwrap(),
+                            ..Default::default()
+                        })
+                        .collect::<Vec<_>>(),
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    let mut tree = widget! {
+        (slots [
+            (item)
+        ])
+    };
+    if let WidgetNode::Component(component) = &mut tree {
+        component
+            .named_slots
+            .insert("0".to_owned(), widget! {(item)});
+    }
+    let mut application = Application::new();
+    application.apply(tree);
+    let ids = application
+        .signals()
+        .read_all()
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect::<HashSet<_>>();
+    assert_eq!(ids.len(), 2);
+}
+
+#[test]
+fn test_states_snapshot() {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+    struct TabsState {
+        open: Vec<String>,
+        scroll: Scalar,
+        pinned: Option<char>,
+        toggled: bool,
+    }
+
+    widget_hook! {
+        use_tabs(life_cycle) {
+            life_cycle.mount(|_, _, _, _, signals| {
+                signals.write(Box::new(()));
+            });
+
+            life_cycle.change(|_, _, state, messenger, _| {
+                for msg in messenger.messages {
+                    if let Some(msg) = msg.downcast_ref::<TabsState>() {
+                        let _ = state.write(msg.clone());
+                    }
+                }
+            });
+        }
+    }
+
+    widget_component! {
+        tabs(id, state) [use_tabs] {
+            let text = match state.read::<TabsState>() {
+                Ok(state) => state.open.join(","),
+                Err(_) => String::new(),
+            };
+
+            widget! {{{
+                TextBox {
+                    id: id.to_owned(),
+                    text,
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    let tree = || {
+        widget! {
+            (#{"tabs"} tabs)
+        }
+    };
+    let state = TabsState {
+        open: vec!["inventory".to_owned(), "map".to_owned()],
+        scroll: 42.5,
+        pinned: Some('m'),
+        toggled: true,
+    };
+
+    let mut application = Application::new();
+    application.states_registry_mut().register::<TabsState>();
+    application.apply(tree());
+    let (id, _) = application.signals().read().unwrap();
+    assert!(application.snapshot_states().states.is_empty());
+    application.messenger().write(id, Box::new(state));
+    application.process();
+    application.process();
+    assert_eq!(rendered_text(&application), "inventory,map");
+    let snapshot = application.snapshot_states();
+    assert_eq!(snapshot.states.len(), 1);
+
+    let json = serde_json::to_string(&snapshot).unwrap();
+    assert_eq!(
+        serde_json::from_str::<StatesSnapshot>(&json).unwrap(),
+        snapshot
+    );
+    let ron = ron::to_string(&snapshot).unwrap();
+    assert_eq!(ron::from_str::<StatesSnapshot>(&ron).unwrap(), snapshot);
+    let yaml = serde_yaml::to_string(&snapshot).unwrap();
+    assert_eq!(
+        serde_yaml::from_str::<StatesSnapshot>(&yaml).unwrap(),
+        snapshot
+    );
+    let binary = bincode::serialize(&snapshot).unwrap();
+    let snapshot = bincode::deserialize::<StatesSnapshot>(&binary).unwrap();
+
+    let mut application = Application::new();
+    assert_eq!(application.restore_states(snapshot.clone()), 0);
+    application.states_registry_mut().register::<TabsState>();
+    assert_eq!(application.restore_states(snapshot.clone()), 1);
+    application.apply(tree());
+    assert_eq!(rendered_text(&application), "inventory,map");
+    let (id, _) = application.signals().read().unwrap();
+
+    application
+        .messenger()
+        .write(id, Box::new(TabsState::default()));
+    application.process();
+    application.process();
+    assert_eq!(rendered_text(&application), "");
+    assert_eq!(application.restore_states(snapshot.clone()), 1);
+    assert!(application.process());
+    assert_eq!(rendered_text(&application), "inventory,map");
+
+    let mut application = Application::new();
+    application.states_registry_mut().register::<TabsState>();
+    assert_eq!(application.restore_states(snapshot), 1);
+    application.apply(widget! {()});
+    application.process();
+    application.apply(tree());
+    assert_eq!(rendered_text(&application), "");
+    assert!(application.snapshot_states().states.is_empty());
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_sync_channels() {
+    use std::thread;
+
+    widget_hook! {
+        use_worker(life_cycle) {
+            life_cycle.mount(|_, _, _, _, signals| {
+                let signals = signals.clone();
+                thread::spawn(move || {
+                    signals.write(Box::new("ready"));
+                })
+                .join()
+                .unwrap();
+            });
+
+            life_cycle.change(|_, _, state, messenger, _| {
+                for msg in messenger.messages {
+                    if let Some(value) = msg.downcast_ref::<usize>() {
+                        let _ = state.write(*value);
+                    }
+                }
+            });
+        }
+    }
+
+    widget_component! {
+        worker(id, state) [use_worker] {
+            widget! {{{
+                TextBox {
+                    id: id.to_owned(),
+                    text: state.read::<usize>().map(|value| value.to_string()).unwrap_or_default(),
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    let mut application = Application::new();
+    application.apply(widget! {
+        (#{"worker"} worker)
+    });
+    let (id, message) = application.signals().read().unwrap();
+    assert_eq!(message.downcast_ref::<&str>(), Some(&"ready"));
+
+    let sender = application.messenger().clone();
+    thread::spawn(move || {
+        sender.write(id, Box::new(42usize));
+    })
+    .join()
+    .unwrap();
+    assert!(application.process());
+    assert!(application.process());
+    match application.rendered_tree() {
+        WidgetUnit::TextBox(text) => assert_eq!(text.text, "42"),
+        _ => panic!("expected text box"),
+    }
+}
+
+#[test]
+fn test_tasks() {
+    use std::{
+        future::Future,
+        pin::Pin,
+        sync::atomic::{AtomicUsize, Ordering},
+        task::{Context, Poll},
+    };
+
+    static IDLE_POLLS: AtomicUsize = AtomicUsize::new(0);
+
+    struct Delay(usize, &'static str);
+
+    impl Future for Delay {
+        type Output = String;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+            if self.0 == 0 {
+                Poll::Ready(self.1.to_owned())
+            } else {
+                self.0 -= 1;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    struct Idle;
+
+    impl Future for Idle {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, _: &mut Context) -> Poll<Self::Output> {
+            IDLE_POLLS.fetch_add(1, Ordering::SeqCst);
+            Poll::Pending
+        }
+    }
+
+    widget_component! {
+        loader(id, state, tasks, life_cycle) {
+            let tasks_ = tasks.clone();
+            life_cycle.mount(move |_, _, _, _, _| {
+                tasks_.spawn(Delay(2, "loaded"));
+                tasks_.spawn(Idle);
+            });
+
+            life_cycle.change(|_, _, state, messenger, _| {
+                for msg in messenger.messages {
+                    if let Some(text) = msg.downcast_ref::<String>() {
+                        let _ = state.write(text.to_owned());
+                    }
+                }
+            });
+
+            widget! {{{
+                TextBox {
+                    id: id.to_owned(),
+                    text: state.read::<String>().cloned().unwrap_or_default(),
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    let mut application = Application::new();
+    application.apply(widget! {
+        (#{"loader"} loader)
+    });
+    assert_eq!(application.pending_tasks(), 2);
+    assert!(!application.process());
+    assert!(!application.process());
+    assert_eq!(rendered_text(&application), "");
+    assert!(application.process());
+    assert!(application.process());
+    assert_eq!(rendered_text(&application), "loaded");
+    assert_eq!(application.pending_tasks(), 1);
+    assert!(!application.process());
+    assert_eq!(IDLE_POLLS.load(Ordering::SeqCst), 1);
+
+    application.apply(widget! {()});
+    assert_eq!(application.pending_tasks(), 0);
+}
+
+#[test]
+fn test_timers() {
+    #[derive(Debug, Default, Copy, Clone, PartialEq)]
+    struct Counters {
+        ticks: usize,
+        intervals: usize,
+        delays: usize,
+    }
+
+    widget_component! {
+        blinker(id, state, timers, life_cycle) {
+            let timers_ = timers.clone();
+            life_cycle.mount(move |_, _, state, _, _| {
+                timers_.tick();
+                timers_.interval(0.5);
+                timers_.delay(1.2);
+                let _ = state.write(Counters::default());
+            });
+
+            life_cycle.change(move |_, _, state, messenger, _| {
+                let mut counters = state.read::<Counters>().copied().unwrap_or_default();
+                for msg in messenger.messages {
+                    match msg.downcast_ref::<TimerMessage>() {
+                        Some(TimerMessage::Tick { .. }) => {
+                            counters.ticks += 1;
+                            timers.tick();
+                        }
+                        Some(TimerMessage::Interval { count, .. }) => counters.intervals += count,
+                        Some(TimerMessage::Delay { .. }) => counters.delays += 1,
+                        None => {}
+                    }
+                }
+                let _ = state.write(counters);
+            });
+
+            let counters = state.read::<Counters>().copied().unwrap_or_default();
+
+            widget! {{{
+                TextBox {
+                    id: id.to_owned(),
+                    text: format!("{} {} {}", counters.ticks, counters.intervals, counters.delays),
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    let mut application = Application::new();
+    application.apply(widget! {
+        (#{"blinker"} blinker)
+    });
+    for _ in 0..4 {
+        application.update(0.25);
+        application.process();
+        application.process();
+    }
+    assert!((application.time() - 1.0).abs() < 1.0e-6);
+    assert_eq!(rendered_text(&application), "4 2 0");
+
+    application.update(1.0);
+    application.process();
+    application.process();
+    assert_eq!(rendered_text(&application), "5 4 1");
+
+    application.apply(widget! {()});
+    assert!(!application.update(1.0));
+}
+
+#[test]
+fn test_animator() {
+    widget_component! {
+        fader(id, animator, life_cycle) {
+            let handle = animator.handle().clone();
+            life_cycle.mount(move |_, _, _, _, _| {
+                handle.play(
+                    "fade",
+                    Animation::Sequence(vec![
+                        Animation::value(
+                            "alpha",
+                            vec![
+                                Keyframe::new(0.0, 0.0, Easing::Linear),
+                                Keyframe::new(1.0, 1.0, Easing::Linear),
+                            ],
+                        ),
+                        Animation::Delay(0.5),
+                    ]),
+                );
+                handle.play(
+                    "pulse",
+                    Animation::Looped(Box::new(Animation::value(
+                        "scale",
+                        vec![
+                            Keyframe::new(0.0, 0.0, Easing::Linear),
+                            Keyframe::new(1.0, 2.0, Easing::InQuad),
+                        ],
+                    ))),
+                );
+            });
+
+            life_cycle.change(|_, _, _, messenger, signals| {
+                for msg in messenger.messages {
+                    if let Some(AnimationMessage::Completed(name)) = msg.downcast_ref() {
+                        signals.write(Box::new(name.to_owned()));
+                    }
+                }
+            });
+
+            widget! {{{
+                TextBox {
+                    id: id.to_owned(),
+                    text: format!(
+                        "{:.2} {:.2}",
+                        animator.value_or("fade", "alpha", -1.0),
+                        animator.value_or("pulse", "scale", -1.0),
+                    ),
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    let mut application = Application::new();
+    application.apply(widget! {
+        (#{"fader"} fader)
+    });
+    assert_eq!(rendered_text(&application), "-1.00 -1.00");
+    assert!(application.process());
+    assert_eq!(rendered_text(&application), "0.00 0.00");
+
+    assert!(application.update(0.5));
+    assert!(application.process());
+    assert_eq!(rendered_text(&application), "0.50 0.50");
+
+    application.update(0.5);
+    application.process();
+    assert_eq!(rendered_text(&application), "1.00 0.00");
+    assert!(application.signals().read().is_none());
+
+    application.update(0.5);
+    application.process();
+    assert_eq!(rendered_text(&application), "1.00 0.50");
+    let (_, name) = application.signals().read().unwrap();
+    assert_eq!(name.downcast_ref::<String>().unwrap(), "fade");
+}
+
+#[test]
+fn test_error_boundary() {
+    use std::{
+        convert::TryInto,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    static HOOK_INITS: AtomicUsize = AtomicUsize::new(0);
+
+    widget_component! {
+        bomb(id, props, hooks) {
+            hooks.use_state(|| HOOK_INITS.fetch_add(1, Ordering::SeqCst));
+            if props.read_cloned_or_default::<bool>() {
+                panic!("boom");
+            }
+            widget! {{{
+                TextBox {
+                    id: id.to_owned(),
+                    text: "safe".to_owned(),
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    widget_component! {
+        list(id, listed_slots) {
+            let items = listed_slots
+                .into_iter()
+                .map(|slot| FlexBoxItem {
+                    slot: slot.try_into().unwrap(),
+                    ..Default::default()
+                })
+                .collect::<Vec<_>>();
+
+            widget! {{{
+                FlexBox {
+                    id: id.to_owned(),
+                    items,
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    let tree = |armed: bool| {
+        let fallback = widget! {{{
+            TextBox {
+                text: "fallback".to_owned(),
+                ..Default::default()
+            }
+        }}};
+        widget! {
+            (#{"list"} list [
+                (#{"boundary"} error_boundary: {ErrorBoundaryProps { fallback }} [
+                    (#{"wrapper"} list [
+                        (#{"bomb"} bomb: {armed})
+                    ])
+                ])
+                (#{"sibling"} bomb)
+            ])
+        }
+    };
+    let bare_tree = |armed: bool| {
+        widget! {
+            (#{"list"} list [
+                (#{"boundary"} error_boundary: {ErrorBoundaryProps::default()} [
+                    (#{"bomb"} bomb: {armed})
+                ])
+            ])
+        }
+    };
+    let texts = |application: &Application| match application.rendered_tree() {
+        WidgetUnit::FlexBox(list) => list
+            .items
+            .iter()
+            .map(|item| match &item.slot {
+                WidgetUnit::TextBox(text) => text.text.to_owned(),
+                WidgetUnit::FlexBox(list) => match &list.items[0].slot {
+                    WidgetUnit::TextBox(text) => text.text.to_owned(),
+                    _ => panic!("expected text box"),
+                },
+                _ => panic!("expected text box"),
+            })
+            .collect::<Vec<_>>(),
+        _ => panic!("expected flex box"),
+    };
+
+    let mut application = Application::new();
+    application.apply(tree(true));
+    assert_eq!(texts(&application), vec!["fallback", "safe"]);
+    let (id, signal) = application.signals().read().unwrap();
+    let signal = signal.downcast_ref::<ErrorBoundarySignal>().unwrap();
+    assert!(id.to_string().ends_with("/boundary"));
+    assert_eq!(signal.id, id);
+    assert!(signal
+        .source
+        .as_ref()
+        .unwrap()
+        .to_string()
+        .ends_with("/boundary/wrapper/bomb"));
+    assert_eq!(signal.message, "boom");
+    assert!(application.signals().read().is_none());
+
+    application.apply(tree(false));
+    assert_eq!(texts(&application), vec!["safe", "safe"]);
+    assert!(application.signals().read().is_none());
+    assert_eq!(HOOK_INITS.load(Ordering::SeqCst), 2);
+
+    let inits = HOOK_INITS.load(Ordering::SeqCst);
+    application.apply(tree(true));
+    assert_eq!(texts(&application), vec!["fallback", "safe"]);
+    assert!(application.signals().read().is_some());
+    application.apply(tree(false));
+    assert_eq!(texts(&application), vec!["safe", "safe"]);
+    assert_eq!(HOOK_INITS.load(Ordering::SeqCst), inits);
+
+    let mut application = Application::new();
+    application.apply(bare_tree(true));
+    let (id, signal) = application.signals().read().unwrap();
+    let signal = signal.downcast_ref::<ErrorBoundarySignal>().unwrap();
+    assert_eq!(signal.id, id);
+    assert!(signal
+        .source
+        .as_ref()
+        .unwrap()
+        .to_string()
+        .ends_with("/boundary/bomb"));
+    match application.rendered_tree() {
+        WidgetUnit::FlexBox(list) => assert!(list.items.is_empty()),
+        _ => panic!("expected flex box"),
+    }
+}
+
+#[test]
+fn test_component_registry() {
+    use serde::Deserialize;
+    use std::convert::TryInto;
+
+    #[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+    struct LabelProps {
+        #[serde(default)]
+        text: String,
+    }
+    implement_props_data!(LabelProps, PartialEq);
+
+    widget_component! {
+        label(id, props) {
+            widget! {{{
+                TextBox {
+                    id: id.to_owned(),
+                    text: props.read_cloned_or_default::<LabelProps>().text,
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    widget_component! {
+        list(id, listed_slots) {
+            let items = listed_slots
+                .into_iter()
+                .map(|slot| FlexBoxItem {
+                    slot: slot.try_into().unwrap(),
+                    ..Default::default()
+                })
+                .collect::<Vec<_>>();
+
+            widget! {{{
+                FlexBox {
+                    id: id.to_owned(),
+                    items,
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    let mut registry = ComponentRegistry::default();
+    registry.register_component("label", label);
+    registry.register_component("list", list);
+    registry.register_props_named::<LabelProps>("LabelProps");
+
+    let json = r#"{"Component": {
+        "type_name": "list",
+        "key": "list",
+        "listed_slots": [
+            {"Component": {
+                "type_name": "label",
+                "key": "a",
+                "props": {"type_name": "LabelProps", "data": {"text": "json"}}
+            }}
+        ]
+    }}"#;
+    let ron = r#"Component((
+        type_name: "list",
+        key: Some("list"),
+        listed_slots: [
+            Component((
+                type_name: "label",
+                key: Some("a"),
+                props: Some((type_name: "LabelProps", data: {"text": "ron"})),
+            )),
+        ],
+    ))"#;
+    let yaml = r#"
+Component:
+  type_name: list
+  key: list
+  listed_slots:
+    - Component:
+        type_name: label
+        key: a
+        props:
+          type_name: LabelProps
+          data:
+            text: yaml
+"#;
+    let trees = vec![
+        registry
+            .deserialize_node(&mut serde_json::Deserializer::from_str(json))
+            .unwrap(),
+        registry
+            .build_node(ron::de::from_str::<WidgetNodeDefinition>(ron).unwrap())
+            .unwrap(),
+        registry
+            .build_node(serde_yaml::from_str::<WidgetNodeDefinition>(yaml).unwrap())
+            .unwrap(),
+    ];
+    for (tree, expected) in trees.into_iter().zip(["json", "ron", "yaml"].iter()) {
+        let mut application = Application::new();
+        application.apply(tree);
+        match application.rendered_tree() {
+            WidgetUnit::FlexBox(list) => match &list.items[0].slot {
+                WidgetUnit::TextBox(text) => assert_eq!(&text.text, expected),
+                _ => panic!("expected text box"),
+            },
+            _ => panic!("expected flex box"),
+        }
+    }
+
+    let unknown = WidgetNodeDefinition::Component(WidgetComponentDefinition {
+        type_name: "button".to_owned(),
+        ..Default::default()
+    });
+    assert_eq!(
+        registry.build_node(unknown),
+        Err(ComponentRegistryError::UnknownComponent(
+            "button".to_owned()
+        ))
+    );
+    let invalid = serde_json::from_str::<WidgetNodeDefinition>(
+        r#"{"Component": {
+            "type_name": "label",
+            "props": {"type_name": "LabelProps", "data": {"text": 42}}
+        }}"#,
+    )
+    .unwrap();
+    assert!(matches!(
+        registry.build_node(invalid),
+        Err(ComponentRegistryError::CouldNotDeserializeProps { .. })
+    ));
+}
+
+#[test]
+#[cfg(all(
+    feature = "binary",
+    feature = "json",
+    feature = "ron",
+    feature = "yaml"
+))]
+fn test_loaders() {
+    let mut application = Application::new();
+    application.apply(widget! {{{
+        FlexBox {
+            items: vec![FlexBoxItem {
+                slot: WidgetUnit::TextBox(TextBox {
+                    text: "hello".to_owned(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }}});
+    let tree = application.rendered_tree().to_owned();
+
+    let json: String = application
+        .render(&mut JsonRenderer { pretty: true })
+        .unwrap();
+    assert_eq!(JsonLoader.load(json.as_str()).unwrap(), tree);
+    let ron: String = application.render(&mut RonRenderer::default()).unwrap();
+    assert_eq!(RonLoader.load(ron.as_str()).unwrap(), tree);
+    let yaml: String = application.render(&mut YamlRenderer).unwrap();
+    assert_eq!(YamlLoader.load(yaml.as_str()).unwrap(), tree);
+    let binary = application.render(&mut BinaryRenderer).unwrap();
+    assert_eq!(BinaryLoader.load(binary.as_slice()).unwrap(), tree);
+
+    let error = JsonLoader.load("{\n  \"TextBox\": 42\n}").unwrap_err();
+    assert!(matches!(
+        error.position,
+        Some(LoaderErrorPosition::LineColumn { line: 2, .. })
+    ));
+    let error = RonLoader.load("TextBox(\n  text: 42,\n)").unwrap_err();
+    assert!(matches!(
+        error.position,
+        Some(LoaderErrorPosition::LineColumn { line: 2, .. })
+    ));
+    let error = YamlLoader.load("TextBox:\n  text: [1, 2]\n").unwrap_err();
+    assert!(matches!(
+        error.position,
+        Some(LoaderErrorPosition::LineColumn { line: 2, .. })
+    ));
+    let error = BinaryLoader.load(&binary[..binary.len() - 1]).unwrap_err();
+    assert_eq!(
+        error.position,
+        Some(LoaderErrorPosition::Offset(binary.len() - 1))
+    );
+}
+
+#[test]
+fn test_diff() {
+    fn text(id: &str, text: &str) -> WidgetUnit {
+        WidgetUnit::TextBox(TextBox {
+            id: WidgetId::new("text".to_owned(), vec![id.to_owned()]),
+            text: text.to_owned(),
+            ..Default::default()
+        })
+    }
+
+    fn list(separation: Scalar, items: Vec<WidgetUnit>) -> WidgetUnit {
+        WidgetUnit::FlexBox(FlexBox {
+            id: WidgetId::new("list".to_owned(), vec!["list".to_owned()]),
+            items: items
+                .into_iter()
+                .map(|slot| FlexBoxItem {
+                    slot,
+                    grow: 1.0,
+                    ..Default::default()
+                })
+                .collect(),
+            separation,
+            ..Default::default()
+        })
+    }
+
+    let from = list(0.0, vec![text("a", "a"), text("b", "b"), text("c", "c")]);
+    let to = list(
+        10.0,
+        vec![
+            text("c", "c"),
+            text("d", "d"),
+            text("a", "A"),
+            text("b", "b"),
+        ],
+    );
+    let patches = diff(&from, &to);
+    assert_eq!(
+        patches
+            .iter()
+            .map(|patch| match patch {
+                WidgetUnitPatch::Insert { .. } => "insert",
+                WidgetUnitPatch::Remove { .. } => "remove",
+                WidgetUnitPatch::Move { .. } => "move",
+                WidgetUnitPatch::Update { .. } => "update",
+                WidgetUnitPatch::Replace { .. } => "replace",
+            })
+            .collect::<Vec<_>>(),
+        vec!["move", "insert", "update", "update"]
+    );
+    match &patches[3] {
+        WidgetUnitPatch::Update { path, .. } => assert_eq!(path, &vec![2]),
+        _ => unreachable!(),
+    }
+    let mut tree = from.clone();
+    apply(&mut tree, &patches).unwrap();
+    assert_eq!(tree, to);
+
+    let to = list(0.0, vec![text("b", "b")]);
+    let patches = diff(&from, &to);
+    assert_eq!(patches.len(), 2);
+    let mut tree = from.clone();
+    apply(&mut tree, &patches).unwrap();
+    assert_eq!(tree, to);
+
+    let wrap = |slot: WidgetUnit| {
+        WidgetUnit::SizeBox(SizeBox {
+            slot: Box::new(slot),
+            ..Default::default()
+        })
+    };
+    let patches = diff(&wrap(text("a", "a")), &wrap(text("b", "b")));
+    assert_eq!(
+        patches,
+        vec![WidgetUnitPatch::Replace {
+            path: vec![0],
+            unit: text("b", "b"),
+        }]
+    );
+    assert!(diff(&from, &from).is_empty());
+    assert_eq!(diff(&from, &text("a", "a")).len(), 1);
+    assert!(apply(&mut text("a", "a"), &diff(&from, &to)).is_err());
+}
+
+#[test]
+#[cfg(feature = "remote")]
+fn test_remote() {
+    use std::io::{Read, Write};
+
+    widget_component! {
+        clicker(id, state, life_cycle) {
+            life_cycle.change(|_, _, state, messenger, _| {
+                let mut count = state.read::<usize>().copied().unwrap_or_default();
+                for msg in messenger.messages {
+                    if let Some(msg) = msg.downcast_ref::<PointerMessage>() {
+                        if msg.action == PointerAction::Click(PointerButton::Primary) {
+                            count += 1;
+                        }
+                    }
+                }
+                let _ = state.write(count);
+            });
+
+            widget! {{{
+                ContentBox {
+                    id: id.to_owned(),
+                    items: vec![ContentBoxItem {
+                        slot: WidgetUnit::TextBox(TextBox {
+                            text: state.read::<usize>().copied().unwrap_or_default().to_string(),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }
+            }}}
+        }
+    }
+
+    let view = Rect {
+        left: 0.0,
+        right: 100.0,
+        top: 0.0,
+        bottom: 100.0,
+    };
+    let (server_stream, client_stream) = MemoryStream::pipe();
+    let mut server = RemoteServer::new(server_stream);
+    let mut client = RemoteClient::new(client_stream);
+    let mut application = Application::new();
+    let mut interactions = DefaultInteractionsEngine::default();
+    application.apply(widget! {
+        (#{"clicker"} clicker)
+    });
+    application
+        .layout(view, &mut DefaultLayoutEngine::default())
+        .unwrap();
+
+    assert!(!client.try_receive().unwrap());
+    assert!(server
+        .sync(application.rendered_tree(), application.layout_data())
+        .unwrap());
+    client.receive().unwrap();
+    assert_eq!(client.tree(), application.rendered_tree());
+    assert_eq!(client.layout(), application.layout_data());
+    assert!(!server
+        .sync(application.rendered_tree(), application.layout_data())
+        .unwrap());
+
+    let position = Vec2 { x: 50.0, y: 50.0 };
+    client
+        .interact(PointerEvent::Down {
+            button: PointerButton::Primary,
+            position,
+        })
+        .unwrap();
+    client
+        .interact(PointerEvent::Up {
+            button: PointerButton::Primary,
+            position,
+        })
+        .unwrap();
+    while let Some(ClientMessage::Interaction(interaction)) = server.try_receive().unwrap() {
+        interactions.interact(interaction);
+    }
+    assert!(application.interact(&mut interactions).unwrap());
+    application.process();
+    application.process();
+    application
+        .layout(view, &mut DefaultLayoutEngine::default())
+        .unwrap();
+
+    assert!(server
+        .sync(application.rendered_tree(), application.layout_data())
+        .unwrap());
+    assert!(client.stream().available() > 0);
+    client.receive().unwrap();
+    assert_eq!(client.tree(), application.rendered_tree());
+    match client.tree() {
+        WidgetUnit::ContentBox(content) => match &content.items[0].slot {
+            WidgetUnit::TextBox(text) => assert_eq!(text.text, "1"),
+            _ => panic!("expected text box"),
+        },
+        _ => panic!("expected content box"),
+    }
+
+    let view = Rect {
+        right: 200.0,
+        ..view
+    };
+    application
+        .layout(view, &mut DefaultLayoutEngine::default())
+        .unwrap();
+    let patch = LayoutPatch::new(client.layout(), application.layout_data());
+    assert_eq!(patch.ui_space, Some(view));
+    assert!(patch.removed.is_empty());
+    assert!(server
+        .sync(application.rendered_tree(), application.layout_data())
+        .unwrap());
+    assert!(client.try_receive().unwrap());
+    assert_eq!(client.layout(), application.layout_data());
+    assert!(LayoutPatch::new(client.layout(), application.layout_data()).is_empty());
+
+    let (server_stream, mut relay) = MemoryStream::pipe();
+    let mut server = RemoteServer::new(server_stream);
+    assert!(server
+        .sync(application.rendered_tree(), application.layout_data())
+        .unwrap());
+    let mut bytes = vec![];
+    relay.read_to_end(&mut bytes).unwrap();
+    let (mut writer, client_stream) = MemoryStream::pipe();
+    let mut client = RemoteClient::new(client_stream);
+    writer.write_all(&bytes[..bytes.len() / 2]).unwrap();
+    assert!(!client.try_receive().unwrap());
+    writer.write_all(&bytes[bytes.len() / 2..]).unwrap();
+    assert!(client.try_receive().unwrap());
+    assert_eq!(client.tree(), application.rendered_tree());
+    assert!(!client.try_receive().unwrap());
+
+    let (mut writer, mut reader) = MemoryStream::pipe();
+    writer.write_all(&u32::MAX.to_le_bytes()).unwrap();
+    assert!(matches!(
+        read_frame::<_, ServerMessage>(&mut reader, DEFAULT_MAX_FRAME_SIZE),
+        Err(RemoteError::FrameTooLarge(size)) if size == u32::MAX as usize
+    ));
+    let mut server = RemoteServer::new(writer).with_max_frame_size(8);
+    assert!(matches!(
+        server.sync(application.rendered_tree(), application.layout_data()),
+        Err(RemoteError::FrameTooLarge(_))
+    ));
+}
+
+#[test]
+fn test_text_measurement() {
+    struct MonospaceMeasurer;
+
+    impl TextMeasurer for MonospaceMeasurer {
+        fn measure_text(&self, text: &str, font: &TextBoxFont, max_width: Option<Scalar>) -> Vec2 {
+            let width = text.chars().count() as Scalar * 10.0;
+            let lines = match max_width {
+                Some(max_width) if max_width > 0.0 => (width / max_width).ceil().max(1.0),
+                _ => 1.0,
+            };
+            Vec2 {
+                x: width.min(max_width.unwrap_or(width)),
+                y: lines * font.size,
+            }
+        }
+    }
+
+    let text = |id: &str, width: TextBoxSizeValue| TextBox {
+        id: WidgetId::new("text".to_owned(), vec![id.to_owned()]),
+        text: "hello".to_owned(),
+        width,
+        height: TextBoxSizeValue::Content,
+        font: TextBoxFont {
+            size: 20.0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let tree = widget! {{{
+        FlexBox {
+            id: WidgetId::new("list".to_owned(), vec!["list".to_owned()]),
+            items: vec![
+                FlexBoxItem {
+                    slot: text("natural", TextBoxSizeValue::Content).into(),
+                    ..Default::default()
+                },
+                FlexBoxItem {
+                    slot: text("narrow", TextBoxSizeValue::Exact(30.0)).into(),
+                    ..Default::default()
+                },
+                FlexBoxItem {
+                    fill: 1.0,
+                    grow: 1.0,
+                    slot: ImageBox {
+                        id: WidgetId::new("image".to_owned(), vec!["image".to_owned()]),
+                        ..Default::default()
+                    }
+                    .into(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }}};
+    let view = Rect {
+        left: 0.0,
+        right: 200.0,
+        top: 0.0,
+        bottom: 100.0,
+    };
+    let size = |application: &Application, id: &str| layout_rect(application, id).size();
+
+    let mut application = Application::new();
+    application.apply(tree);
+    application
+        .layout(
+            view,
+            &mut DefaultLayoutEngine::new().with_text_measurer(MonospaceMeasurer),
+        )
+        .unwrap();
+    assert_eq!(size(&application, "natural"), Vec2 { x: 50.0, y: 20.0 });
+    assert_eq!(size(&application, "narrow"), Vec2 { x: 30.0, y: 40.0 });
+    assert_eq!(size(&application, "image"), Vec2 { x: 120.0, y: 100.0 });
+
+    application
+        .layout(view, &mut DefaultLayoutEngine::default())
+        .unwrap();
+    assert_eq!(size(&application, "natural"), Vec2 { x: 0.0, y: 0.0 });
+
+    #[cfg(feature = "ab_glyph")]
+    {
+        let mut measurer = AbGlyphTextMeasurer::default();
+        measurer
+            .load_font(
+                "verdana",
+                include_bytes!("../demos/hello-world/resources/verdana.ttf").to_vec(),
+            )
+            .unwrap();
+        let font = TextBoxFont {
+            name: "verdana".to_owned(),
+            size: 20.0,
+            ..Default::default()
+        };
+        let line = measurer.measure_text("hello world", &font, None);
+        assert!(line.x > 0.0 && line.y > 0.0);
+        let wrapped = measurer.measure_text("hello world", &font, Some(line.x * 0.75));
+        assert!(wrapped.x < line.x);
+        assert!(wrapped.y > line.y * 1.5);
+        let unknown = TextBoxFont {
+            name: "unknown".to_owned(),
+            ..font
+        };
+        assert_eq!(
+            measurer.measure_text("hello world", &unknown, None),
+            Vec2::default()
+        );
+    }
+}
+
+#[test]
+fn test_image_size_provider() {
+    let image = |id: &str, size: ImageBoxSizeValue, aspect: Option<ImageBoxAspectRatio>| ImageBox {
+        id: WidgetId::new("image".to_owned(), vec![id.to_owned()]),
+        width: size,
+        height: size,
+        content_keep_aspect_ratio: aspect,
+        material: ImageBoxMaterial::Image(ImageBoxImage {
+            id: "logo".to_owned(),
+            ..Default::default()
+        }),
+    };
+    let tree = widget! {{{
+        FlexBox {
+            items: vec![
+                FlexBoxItem {
+                    slot: image("natural", ImageBoxSizeValue::Content, None).into(),
+                    ..Default::default()
+                },
+                FlexBoxItem {
+                    fill: 1.0,
+                    grow: 1.0,
+                    slot: image(
+                        "fitted",
+                        ImageBoxSizeValue::Fill,
+                        Some(ImageBoxAspectRatio {
+                            horizontal_alignment: 0.5,
+                            vertical_alignment: 0.5,
+                        }),
+                    )
+                    .into(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }}};
+    let view = Rect {
+        left: 0.0,
+        right: 300.0,
+        top: 0.0,
+        bottom: 100.0,
+    };
+    let mut sizes = std::collections::HashMap::new();
+    sizes.insert("logo".to_owned(), Vec2 { x: 50.0, y: 50.0 });
+    let mut application = Application::new();
+    application.apply(tree);
+    application
+        .layout(
+            view,
+            &mut DefaultLayoutEngine::default().with_image_size_provider(&sizes),
+        )
+        .unwrap();
+    assert_eq!(
+        layout_rect(&application, "natural"),
+        Rect {
+            left: 0.0,
+            right: 50.0,
+            top: 0.0,
+            bottom: 50.0,
+        }
+    );
+    assert_eq!(
+        layout_rect(&application, "fitted"),
+        Rect {
+            left: 125.0,
+            right: 225.0,
+            top: 0.0,
+            bottom: 100.0,
+        }
+    );
+
+    let tree = widget! {{{
+        ContentBox {
+            items: vec![ContentBoxItem {
+                slot: ImageBox {
+                    width: ImageBoxSizeValue::Exact(200.0),
+                    height: ImageBoxSizeValue::Exact(100.0),
+                    ..image(
+                        "aligned",
+                        ImageBoxSizeValue::Fill,
+                        Some(ImageBoxAspectRatio {
+                            horizontal_alignment: 0.0,
+                            vertical_alignment: 0.5,
+                        }),
+                    )
+                }
+                .into(),
+                layout: ContentBoxItemLayout {
+                    anchors: Rect {
+                        left: 0.0,
+                        right: 1.0,
+                        top: 0.0,
+                        bottom: 1.0,
+                    },
+                    align: Vec2 { x: 1.0, y: 0.0 },
+                    ..Default::default()
+                },
+            }],
+            ..Default::default()
+        }
+    }}};
+    application.apply(tree);
+    application
+        .layout(
+            view,
+            &mut DefaultLayoutEngine::default().with_image_size_provider(&sizes),
+        )
+        .unwrap();
+    assert_eq!(
+        layout_rect(&application, "aligned"),
+        Rect {
+            left: 100.0,
+            right: 200.0,
+            top: 0.0,
+            bottom: 100.0,
+        }
+    );
+}
+
+#[test]
+fn test_grid_tracks() {
+    let cell = |id: &str, col: Integer, row: Integer, cols: Integer| GridBoxItem {
+        slot: ImageBox {
+            id: WidgetId::new("cell".to_owned(), vec![id.to_owned()]),
+            width: ImageBoxSizeValue::Exact(40.0),
+            height: ImageBoxSizeValue::Exact(30.0),
+            ..Default::default()
+        }
+        .into(),
+        space_occupancy: IntRect {
+            left: col,
+            right: col + cols,
+            top: row,
+            bottom: row + 1,
+        },
+        ..Default::default()
+    };
+    let tree = widget! {{{
+        GridBox {
+            items: vec![
+                cell("icon", 0, 0, 1),
+                cell("name", 1, 0, 1),
+                cell("value", 2, 0, 1),
+                cell("footer", 0, 1, 3),
+            ],
+            col_tracks: vec![
+                GridBoxTrackSize::Content.into(),
+                GridBoxTrackSize::Fraction(2.0).into(),
+                GridBoxTrack {
+                    size: GridBoxTrackSize::Fraction(1.0),
+                    max: Some(50.0),
+                    ..Default::default()
+                },
+            ],
+            row_tracks: vec![
+                GridBoxTrackSize::Exact(60.0).into(),
+                GridBoxTrack {
+                    size: GridBoxTrackSize::Fraction(1.0),
+                    min: 200.0,
+                    ..Default::default()
+                },
+            ],
+            col_gap: 10.0,
+            row_gap: 5.0,
+            ..Default::default()
+        }
+    }}};
+    let view = Rect {
+        left: 0.0,
+        right: 320.0,
+        top: 0.0,
+        bottom: 100.0,
+    };
+    let mut application = Application::new();
+    application.apply(tree);
+    application
+        .layout(view, &mut DefaultLayoutEngine::default())
+        .unwrap();
+    assert_eq!(layout_rect(&application, "icon").left, 0.0);
+    assert_eq!(layout_rect(&application, "name").left, 50.0);
+    assert_eq!(layout_rect(&application, "value").left, 270.0);
+    assert_eq!(layout_rect(&application, "footer").top, 65.0);
+    assert_eq!(layout_rect(&application, "footer").width(), 40.0);
+}
+
+#[test]
+fn test_grid_auto_flow() {
+    let cell = |id: &str, col_span: usize, row_span: usize| GridBoxItem {
+        slot: ImageBox {
+            id: WidgetId::new("cell".to_owned(), vec![id.to_owned()]),
+            ..Default::default()
+        }
+        .into(),
+        col_span,
+        row_span,
+        ..Default::default()
+    };
+    let rect = |left, right, top, bottom| IntRect {
+        left,
+        right,
+        top,
+        bottom,
+    };
+
+    let grid = GridBox {
+        items: vec![
+            cell("a", 0, 0),
+            GridBoxItem {
+                space_occupancy: rect(1, 2, 0, 1),
+                ..cell("b", 0, 0)
+            },
+            cell("c", 2, 1),
+            cell("d", 1, 1),
+            cell("e", 1, 1),
+        ],
+        cols: 3,
+        auto_flow: GridBoxAutoFlow::Row,
+        ..Default::default()
+    };
+    assert_eq!(
+        grid.space_occupancies(),
+        vec![
+            rect(0, 1, 0, 1),
+            rect(1, 2, 0, 1),
+            rect(0, 2, 1, 2),
+            rect(2, 3, 1, 2),
+            rect(0, 1, 2, 3),
+        ]
+    );
+
+    let mut application = Application::new();
+    application.apply(WidgetUnit::from(grid).into());
+    application
+        .layout(
+            Rect {
+                left: 0.0,
+                right: 300.0,
+                top: 0.0,
+                bottom: 300.0,
+            },
+            &mut DefaultLayoutEngine::default(),
+        )
+        .unwrap();
+    assert_eq!(
+        layout_rect(&application, "e"),
+        Rect {
+            left: 0.0,
+            right: 100.0,
+            top: 200.0,
+            bottom: 300.0,
+        }
+    );
+
+    let grid = GridBox {
+        items: vec![cell("a", 1, 1), cell("b", 1, 2), cell("c", 1, 1)],
+        rows: 2,
+        auto_flow: GridBoxAutoFlow::Column,
+        ..Default::default()
+    };
+    assert_eq!(
+        grid.space_occupancies(),
+        vec![rect(0, 1, 0, 1), rect(1, 2, 0, 2), rect(2, 3, 0, 1)]
+    );
+}
+
+#[test]
+fn test_flex_distribution() {
+    let layout = |justify_content, align_content, wrap, size: Vec2| {
+        let count = if wrap { 4 } else { 3 };
+        let items = (0..count)
+            .map(|index| FlexBoxItem {
+                slot: ImageBox {
+                    id: WidgetId::new("item".to_owned(), vec![index.to_string()]),
+                    width: ImageBoxSizeValue::Exact(50.0),
+                    height: ImageBoxSizeValue::Exact(50.0),
+                    ..Default::default()
+                }
+                .into(),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let tree = widget! {{{
+            FlexBox {
+                id: WidgetId::new("flex".to_owned(), vec![]),
+                items,
+                separation: 10.0,
+                wrap,
+                justify_content,
+                align_content,
+                ..Default::default()
+            }
+        }}};
+        let mut application = Application::new();
+        application.apply(tree);
+        application
+            .layout(
+                Rect {
+                    left: 0.0,
+                    right: size.x,
+                    top: 0.0,
+                    bottom: size.y,
+                },
+                &mut DefaultLayoutEngine::default(),
+            )
+            .unwrap();
+        let container = layout_rects(&application, "flex")[0];
+        (
+            layout_rects(&application, "item")
+                .into_iter()
+                .map(|rect| (rect.left, rect.top))
+                .collect::<Vec<_>>(),
+            (container.width(), container.height()),
+        )
+    };
+    let size = Vec2 { x: 300.0, y: 50.0 };
+
+    assert_eq!(
+        layout(
+            FlexBoxDistribution::Start,
+            FlexBoxDistribution::Start,
+            false,
+            size
+        ),
+        (vec![(0.0, 0.0), (60.0, 0.0), (120.0, 0.0)], (300.0, 50.0))
+    );
+    assert_eq!(
+        layout(
+            FlexBoxDistribution::Center,
+            FlexBoxDistribution::Start,
+            false,
+            size
+        ),
+        (vec![(65.0, 0.0), (125.0, 0.0), (185.0, 0.0)], (300.0, 50.0))
+    );
+    assert_eq!(
+        layout(
+            FlexBoxDistribution::SpaceBetween,
+            FlexBoxDistribution::Start,
+            false,
+            size
+        ),
+        (vec![(0.0, 0.0), (125.0, 0.0), (250.0, 0.0)], (300.0, 50.0))
+    );
+    assert_eq!(
+        layout(
+            FlexBoxDistribution::SpaceEvenly,
+            FlexBoxDistribution::Start,
+            false,
+            size
+        ),
+        (vec![(32.5, 0.0), (125.0, 0.0), (217.5, 0.0)], (300.0, 50.0))
+    );
+    assert_eq!(
+        layout(
+            FlexBoxDistribution::Start,
+            FlexBoxDistribution::Start,
+            true,
+            Vec2 { x: 120.0, y: 200.0 }
+        ),
+        (
+            vec![(0.0, 0.0), (60.0, 0.0), (0.0, 60.0), (60.0, 60.0)],
+            (120.0, 200.0)
+        )
+    );
+    assert_eq!(
+        layout(
+            FlexBoxDistribution::End,
+            FlexBoxDistribution::End,
+            true,
+            Vec2 { x: 120.0, y: 200.0 }
+        ),
+        (
+            vec![(10.0, 90.0), (70.0, 90.0), (10.0, 150.0), (70.0, 150.0)],
+            (120.0, 200.0)
+        )
+    );
+    assert_eq!(
+        layout(
+            FlexBoxDistribution::SpaceAround,
+            FlexBoxDistribution::SpaceBetween,
+            true,
+            Vec2 { x: 120.0, y: 200.0 }
+        ),
+        (
+            vec![(2.5, 0.0), (67.5, 0.0), (2.5, 150.0), (67.5, 150.0)],
+            (120.0, 200.0)
+        )
+    );
+}
+
+#[test]
+fn test_size_constraints() {
+    let layout = |tree: WidgetNode, width: Scalar| {
+        let mut application = Application::new();
+        application.apply(tree);
+        application
+            .layout(
+                Rect {
+                    left: 0.0,
+                    right: width,
+                    top: 0.0,
+                    bottom: 100.0,
+                },
+                &mut DefaultLayoutEngine::default(),
+            )
+            .unwrap();
+        layout_rects(&application, "item")
+    };
+    let id = |key: &str| WidgetId::new("item".to_owned(), vec![key.to_owned()]);
+    let image = |key: &str| ImageBox {
+        id: id(key),
+        width: ImageBoxSizeValue::Fill,
+        height: ImageBoxSizeValue::Fill,
+        ..Default::default()
+    };
+
+    let tree = widget! {{{
+        SizeBox {
+            id: id("panel"),
+            width: SizeBoxSizeValue::Fill,
+            height: SizeBoxSizeValue::Fill,
+            max_width: Some(300.0),
+            min_height: 150.0,
+            margin: Rect {
+                left: 10.0,
+                right: 10.0,
+                top: 5.0,
+                bottom: 5.0,
+            },
+            ..Default::default()
+        }
+    }}};
+    assert_eq!(
+        layout(tree, 1000.0),
+        vec![Rect {
+            left: 0.0,
+            right: 320.0,
+            top: 0.0,
+            bottom: 160.0,
+        }]
+    );
+
+    let tree = widget! {{{
+        SizeBox {
+            id: id("panel"),
+            width: SizeBoxSizeValue::Fill,
+            height: SizeBoxSizeValue::Content,
+            min_width: 100.0,
+            max_height: Some(50.0),
+            margin: Rect {
+                left: 5.0,
+                right: 5.0,
+                ..Default::default()
+            },
+            slot: Box::new(image("inner").into()),
+            ..Default::default()
+        }
+    }}};
+    assert_eq!(
+        layout(tree, 40.0),
+        vec![
+            Rect {
+                left: 5.0,
+                right: 105.0,
+                top: 0.0,
+                bottom: 50.0,
+            },
+            Rect {
+                left: 0.0,
+                right: 110.0,
+                top: 0.0,
+                bottom: 50.0,
+            },
+        ]
+    );
+
+    let tree = widget! {{{
+        FlexBox {
+            items: vec![
+                FlexBoxItem {
+                    slot: SizeBox {
+                        id: id("a"),
+                        width: SizeBoxSizeValue::Fill,
+                        height: SizeBoxSizeValue::Fill,
+                        min_width: 50.0,
+                        margin: Rect {
+                            left: 5.0,
+                            right: 5.0,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    }
+                    .into(),
+                    ..Default::default()
+                },
+                FlexBoxItem {
+                    slot: image("b").into(),
+                    grow: 1.0,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }}};
+    let rects = layout(tree, 200.0);
+    assert_eq!(
+        rects
+            .iter()
+            .map(|rect| (rect.left, rect.width()))
+            .collect::<Vec<_>>(),
+        vec![(0.0, 60.0), (60.0, 140.0)]
+    );
+
+    let tree = widget! {{{
+        FlexBox {
+            items: vec![
+                FlexBoxItem {
+                    slot: SizeBox {
+                        id: id("a"),
+                        width: SizeBoxSizeValue::Fill,
+                        height: SizeBoxSizeValue::Fill,
+                        max_width: Some(200.0),
+                        ..Default::default()
+                    }
+                    .into(),
+                    grow: 1.0,
+                    fill: 1.0,
+                    ..Default::default()
+                },
+                FlexBoxItem {
+                    slot: image("b").into(),
+                    grow: 1.0,
+                    fill: 1.0,
+                    max_height: Some(40.0),
+                    ..Default::default()
+                },
+                FlexBoxItem {
+                    slot: image("c").into(),
+                    grow: 1.0,
+                    fill: 1.0,
+                    min_width: 100.0,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }}};
+    let rects = layout(tree, 1000.0);
+    assert_eq!(
+        rects
+            .iter()
+            .map(|rect| (rect.left, rect.width(), rect.height()))
+            .collect::<Vec<_>>(),
+        vec![
+            (0.0, 200.0, 100.0),
+            (200.0, 350.0, 40.0),
+            (550.0, 450.0, 100.0)
+        ]
+    );
+
+    let tree = widget! {{{
+        FlexBox {
+            items: vec![
+                FlexBoxItem {
+                    slot: image("a").into(),
+                    basis: Some(150.0),
+                    shrink: 1.0,
+                    min_width: 120.0,
+                    ..Default::default()
+                },
+                FlexBoxItem {
+                    slot: image("b").into(),
+                    basis: Some(150.0),
+                    shrink: 1.0,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }}};
+    let rects = layout(tree, 200.0);
+    assert_eq!(
+        rects
+            .iter()
+            .map(|rect| (rect.left, rect.width()))
+            .collect::<Vec<_>>(),
+        vec![(0.0, 120.0), (120.0, 80.0)]
+    );
+}